@@ -0,0 +1,232 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use crate::solver::{Answer, Part};
+use crate::utils::read_input;
+
+pub const ANSWERS_FILENAME: &str = "answers.toml";
+
+// Lives next to Cargo.toml, anchored at compile time like the input search dirs
+pub fn default_answers_path() -> PathBuf {
+  Path::new(env!("CARGO_MANIFEST_DIR")).join(ANSWERS_FILENAME)
+}
+
+// How a result compares to the known answer for its day, input and part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+  Pass,
+  Fail { expected: String },
+  Unknown,
+}
+
+impl Verification {
+  pub fn of(expected: Option<&str>, answer: &Answer) -> Verification {
+    match expected {
+      Some(expected) if expected == answer.to_string() => Verification::Pass,
+      Some(expected) => Verification::Fail {
+        expected: expected.to_string(),
+      },
+      None => Verification::Unknown,
+    }
+  }
+
+  // Unknown answers can't be verified either way
+  pub fn passed(&self) -> Option<bool> {
+    match self {
+      Verification::Pass => Some(true),
+      Verification::Fail { .. } => Some(false),
+      Verification::Unknown => None,
+    }
+  }
+}
+
+impl fmt::Display for Verification {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Verification::Pass => write!(f, "pass"),
+      Verification::Fail { expected } => write!(f, "fail, expected {}", expected),
+      Verification::Unknown => write!(f, "unknown"),
+    }
+  }
+}
+
+// Answers are keyed by day, the input's file name and part. The TOML layout is
+//
+//   [day_1."input.txt"]
+//   part_1 = 55123
+//
+// Answers can be integers or strings, and are compared in their printed form
+#[derive(Debug, Default)]
+pub struct KnownAnswers {
+  answers: BTreeMap<(u8, String, Part), String>,
+}
+
+fn invalid_data(message: String) -> Error {
+  Error::new(ErrorKind::InvalidData, message)
+}
+
+fn number_from_key(key: &str, prefix: &str) -> Option<u8> {
+  key.strip_prefix(prefix)?.parse::<u8>().ok()
+}
+
+impl KnownAnswers {
+  pub fn from_toml(contents: &str) -> Result<KnownAnswers, Error> {
+    let table = contents
+      .parse::<toml::Table>()
+      .map_err(|error| invalid_data(format!("invalid answers file: {}", error)))?;
+
+    let mut answers = BTreeMap::new();
+
+    for (day_key, inputs) in table.iter() {
+      let day = number_from_key(day_key, "day_")
+        .ok_or_else(|| invalid_data(format!("expected a day_N table, found {:?}", day_key)))?;
+      let inputs = inputs
+        .as_table()
+        .ok_or_else(|| invalid_data(format!("expected {} to be a table of inputs", day_key)))?;
+
+      for (input, parts) in inputs.iter() {
+        let parts = parts.as_table().ok_or_else(|| {
+          invalid_data(format!(
+            "expected {}.{:?} to be a table of parts",
+            day_key, input
+          ))
+        })?;
+
+        for (part_key, answer) in parts.iter() {
+          let part = number_from_key(part_key, "part_")
+            .and_then(Part::from_number)
+            .ok_or_else(|| {
+              invalid_data(format!("expected part_1 or part_2, found {:?}", part_key))
+            })?;
+          let answer = match answer {
+            toml::Value::Integer(answer) => answer.to_string(),
+            toml::Value::String(answer) => answer.clone(),
+            other => {
+              return Err(invalid_data(format!(
+                "expected an integer or string answer for {}.{:?}.{}, found {}",
+                day_key, input, part_key, other
+              )))
+            }
+          };
+
+          answers.insert((day, input.clone(), part), answer);
+        }
+      }
+    }
+
+    Ok(KnownAnswers { answers })
+  }
+
+  // An explicitly requested file has to exist, while a missing default file just means
+  // nothing is known yet
+  pub fn load(explicit_path: Option<&Path>) -> Result<KnownAnswers, Error> {
+    match explicit_path {
+      Some(path) => {
+        let contents = read_input(path).map_err(|error| {
+          Error::new(
+            error.kind(),
+            format!("could not read {}: {}", path.display(), error),
+          )
+        })?;
+        KnownAnswers::from_toml(&contents)
+      }
+      None => match read_input(default_answers_path()) {
+        Ok(contents) => KnownAnswers::from_toml(&contents),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(KnownAnswers::default()),
+        Err(error) => Err(error),
+      },
+    }
+  }
+
+  pub fn get(&self, day: u8, input: &str, part: Part) -> Option<&str> {
+    self
+      .answers
+      .get(&(day, input.to_string(), part))
+      .map(|answer| answer.as_str())
+  }
+
+  // Every known answer as (day, input, part, answer), in day order
+  pub fn iter(&self) -> impl Iterator<Item = (u8, &str, Part, &str)> {
+    self
+      .answers
+      .iter()
+      .map(|((day, input, part), answer)| (*day, input.as_str(), *part, answer.as_str()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{KnownAnswers, Verification};
+  use crate::solver::{find_solver, Answer, Part};
+  use crate::utils::read_day_input;
+
+  #[test]
+  pub fn answers_file_parses() {
+    let answers = KnownAnswers::from_toml(
+      "[day_9.\"input.txt\"]\npart_1 = 2043183816\n\n[day_9.\"other.txt\"]\npart_2 = \"-3\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(answers.get(9, "input.txt", Part::One), Some("2043183816"));
+    assert_eq!(answers.get(9, "other.txt", Part::Two), Some("-3"));
+    assert_eq!(answers.get(9, "input.txt", Part::Two), None);
+  }
+
+  #[test]
+  pub fn malformed_answers_are_rejected() {
+    assert!(KnownAnswers::from_toml("[day_x.\"input.txt\"]\npart_1 = 1\n").is_err());
+    assert!(KnownAnswers::from_toml("[day_1.\"input.txt\"]\npart_3 = 1\n").is_err());
+    assert!(KnownAnswers::from_toml("[day_1.\"input.txt\"]\npart_1 = 1.5\n").is_err());
+  }
+
+  #[test]
+  pub fn results_are_verified() {
+    let answers = KnownAnswers::from_toml("[day_2.\"input.txt\"]\npart_1 = 2679\n").unwrap();
+
+    assert_eq!(
+      Verification::of(
+        answers.get(2, "input.txt", Part::One),
+        &Answer::from(2679u32)
+      ),
+      Verification::Pass
+    );
+    assert_eq!(
+      Verification::of(answers.get(2, "input.txt", Part::One), &Answer::from(12u32)),
+      Verification::Fail {
+        expected: String::from("2679")
+      }
+    );
+    assert_eq!(
+      Verification::of(answers.get(2, "input.txt", Part::Two), &Answer::from(12u32)),
+      Verification::Unknown
+    );
+  }
+
+  // Solves every entry in answers.toml, reporting all of the mismatches at once
+  #[test]
+  pub fn known_answers_are_still_produced() {
+    let answers = KnownAnswers::load(None).unwrap();
+    assert!(answers.iter().next().is_some(), "answers.toml is empty");
+
+    let failures = answers
+      .iter()
+      .filter_map(|(day, input, part, expected)| {
+        let solver = find_solver(day).unwrap();
+        let contents = read_day_input(day, input).unwrap();
+        let result = solver.solve(part, &contents).unwrap();
+
+        match result.map(|answer| answer.to_string()) {
+          Some(result) if result == expected => None,
+          result => Some(format!(
+            "day {} {} {}: expected {}, got {:?}",
+            day, input, part, expected, result
+          )),
+        }
+      })
+      .collect::<Vec<_>>();
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+  }
+}
@@ -0,0 +1,140 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::solver::Part;
+
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+  let start = Instant::now();
+  let result = f();
+  (result, start.elapsed())
+}
+
+// Summary of repeated timings of the same thing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+  pub min: Duration,
+  pub median: Duration,
+  pub max: Duration,
+}
+
+impl Timings {
+  // None if there is nothing to summarise. An even number of timings uses the mean of the
+  // middle two as the median
+  pub fn from_durations(durations: &[Duration]) -> Option<Timings> {
+    let mut sorted = durations.to_vec();
+    sorted.sort();
+
+    let middle = sorted.len() / 2;
+    let median = if sorted.len().is_multiple_of(2) {
+      (*sorted.get(middle.checked_sub(1)?)? + sorted[middle]) / 2
+    } else {
+      sorted[middle]
+    };
+
+    Some(Timings {
+      min: *sorted.first()?,
+      median,
+      max: *sorted.last()?,
+    })
+  }
+}
+
+fn milliseconds(duration: Duration) -> f64 {
+  duration.as_secs_f64() * 1000.0
+}
+
+// One part's benchmark, in the shape written out by `bench --json`. The parse/solve split is
+// only known for days that can parse their input on its own, see Solver::parse
+#[derive(Debug, Serialize)]
+pub struct BenchRecord {
+  pub day: u8,
+  pub part: u8,
+  pub runs: usize,
+  pub min_ms: f64,
+  pub median_ms: f64,
+  pub max_ms: f64,
+  pub parse_median_ms: Option<f64>,
+  pub solve_median_ms: Option<f64>,
+}
+
+impl BenchRecord {
+  // `parse` and `solve` are the median split of the total time, when the day has one
+  pub fn new(
+    day: u8,
+    part: Part,
+    runs: usize,
+    total: Timings,
+    split: Option<(Duration, Duration)>,
+  ) -> BenchRecord {
+    BenchRecord {
+      day,
+      part: part.number(),
+      runs,
+      min_ms: milliseconds(total.min),
+      median_ms: milliseconds(total.median),
+      max_ms: milliseconds(total.max),
+      parse_median_ms: split.map(|(parse, _)| milliseconds(parse)),
+      solve_median_ms: split.map(|(_, solve)| milliseconds(solve)),
+    }
+  }
+}
+
+impl fmt::Display for BenchRecord {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "day {} part_{}: min {:.3}ms, median {:.3}ms, max {:.3}ms over {} runs",
+      self.day, self.part, self.min_ms, self.median_ms, self.max_ms, self.runs
+    )?;
+
+    if let (Some(parse), Some(solve)) = (self.parse_median_ms, self.solve_median_ms) {
+      write!(f, " (parse {:.3}ms, solve {:.3}ms)", parse, solve)?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Duration;
+
+  use super::{BenchRecord, Timings};
+  use crate::solver::Part;
+
+  fn millis(values: &[u64]) -> Vec<Duration> {
+    values
+      .iter()
+      .map(|value| Duration::from_millis(*value))
+      .collect()
+  }
+
+  #[test]
+  pub fn timings_summarise_unsorted_durations() {
+    let timings = Timings::from_durations(&millis(&[5, 1, 3])).unwrap();
+    assert_eq!(timings.min, Duration::from_millis(1));
+    assert_eq!(timings.median, Duration::from_millis(3));
+    assert_eq!(timings.max, Duration::from_millis(5));
+  }
+
+  #[test]
+  pub fn even_timings_use_the_middle_two_for_the_median() {
+    let timings = Timings::from_durations(&millis(&[4, 1, 2, 8])).unwrap();
+    assert_eq!(timings.median, Duration::from_millis(3));
+    assert_eq!(Timings::from_durations(&[]), None);
+  }
+
+  #[test]
+  pub fn records_serialize_to_json() {
+    let total = Timings::from_durations(&millis(&[2])).unwrap();
+    let split = Some((Duration::from_millis(1), Duration::from_millis(1)));
+    let record = BenchRecord::new(3, Part::Two, 1, total, split);
+
+    assert_eq!(
+      serde_json::to_string(&record).unwrap(),
+      "{\"day\":3,\"part\":2,\"runs\":1,\"min_ms\":2.0,\"median_ms\":2.0,\"max_ms\":2.0,\"parse_median_ms\":1.0,\"solve_median_ms\":1.0}"
+    );
+  }
+}
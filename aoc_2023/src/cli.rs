@@ -0,0 +1,397 @@
+use std::path::PathBuf;
+
+use clap::builder::{PossibleValue, PossibleValuesParser, TypedValueParser};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+
+use crate::day_10::render::{Charset, Overlay};
+use crate::image::ImageFormat;
+use crate::logging::TargetFilter;
+use crate::solver::Part;
+
+#[derive(Debug, Parser)]
+#[command(name = "aoc_2023", about = "Advent of Code 2023 solutions")]
+pub struct Cli {
+  // Running without a subcommand runs every day, same as `run`
+  #[command(subcommand)]
+  pub command: Option<Command>,
+
+  #[command(flatten)]
+  pub log_args: LogArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct LogArgs {
+  /// Show more diagnostic output, repeat for more detail
+  #[arg(short, long, action = ArgAction::Count, global = true)]
+  pub verbose: u8,
+
+  /// Show less diagnostic output, repeat to silence warnings and errors too
+  #[arg(short, long, action = ArgAction::Count, global = true, conflicts_with = "verbose")]
+  pub quiet: u8,
+
+  /// Show diagnostic output for one target, e.g. "day_5" for everything from day 5 or
+  /// "day_5=debug" for a specific level. Can be repeated
+  #[arg(long = "log", value_name = "TARGET[=LEVEL]", global = true)]
+  pub target_filters: Vec<TargetFilter>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+  /// Solve one day, or every day if --day is left out
+  Run(RunArgs),
+  /// List every available day
+  List,
+  /// Solve days and report which ones fail
+  Check(CheckArgs),
+  /// Time how long each day takes to solve
+  Bench(BenchArgs),
+  /// Draw day 10's pipe map, highlighting the loop
+  Render(RenderArgs),
+  /// Save day 3's schematic or day 10's pipe map as an image
+  Export(ExportArgs),
+}
+
+// An explicit --input can only be one day's input
+#[derive(Debug, Args, Default)]
+#[command(mut_arg("input", |input| input.requires("day")))]
+pub struct SolveArgs {
+  /// Day to solve (1-25)
+  #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+  pub day: Option<u8>,
+
+  /// Part to solve (1 or 2), both parts are solved if left out
+  #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+  pub part: Option<u8>,
+
+  #[command(flatten)]
+  pub input_args: InputArgs,
+
+  /// Answers file to verify results against. Defaults to answers.toml in the crate directory
+  #[arg(long)]
+  pub answers: Option<PathBuf>,
+}
+
+// Where puzzle input is read from, shared by every command that solves or draws a day
+#[derive(Debug, Args, Clone, Default)]
+pub struct InputArgs {
+  /// Read puzzle input from this file instead of the day's input.txt, or "-" for stdin
+  #[arg(short, long, conflicts_with = "example")]
+  pub input: Option<PathBuf>,
+
+  /// Directory holding a day_N directory of inputs for each day. Defaults to $AOC_INPUT_DIR,
+  /// falling back to the crate's src directory
+  #[arg(long)]
+  pub inputs_dir: Option<PathBuf>,
+
+  /// Use the day's example input instead of the real puzzle input
+  #[arg(short, long)]
+  pub example: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+  #[default]
+  Text,
+  Json,
+}
+
+#[derive(Debug, Args, Default)]
+pub struct RunArgs {
+  #[command(flatten)]
+  pub solve_args: SolveArgs,
+
+  /// How to print the results
+  #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+  pub format: OutputFormat,
+
+  /// Solve every day and part concurrently on N threads, or one per CPU if N is left out.
+  /// Results are still printed in day order
+  #[arg(short, long, value_name = "N", num_args = 0..=1, default_missing_value = "0")]
+  pub jobs: Option<usize>,
+
+  /// Stream the input a line at a time instead of reading it all up front, for inputs too big to
+  /// hold in memory. Only days 1, 2, 4 and 9 can solve a line at a time, the rest still read
+  /// everything. Streaming stdin needs --part
+  #[arg(long)]
+  pub stream: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct CheckArgs {
+  #[command(flatten)]
+  pub solve_args: SolveArgs,
+
+  /// Check every embedded example against its known answers instead of solving the inputs
+  #[arg(long, conflicts_with_all = ["input", "example", "inputs_dir", "answers"])]
+  pub examples: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+  #[command(flatten)]
+  pub solve_args: SolveArgs,
+
+  /// Number of times to solve each part
+  #[arg(short = 'n', long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+  pub runs: u32,
+
+  /// Print the results as JSON instead of text
+  #[arg(long)]
+  pub json: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+  /// Only when printing to a terminal
+  #[default]
+  Auto,
+  Always,
+  Never,
+}
+
+#[derive(Debug, Args)]
+pub struct RenderArgs {
+  #[command(flatten)]
+  pub input_args: InputArgs,
+
+  /// Characters to draw pipes with
+  #[arg(long, value_enum, default_value_t = Charset::Unicode)]
+  pub charset: Charset,
+
+  /// Mark tiles inside and outside the loop, or shade the loop by distance from the start
+  #[arg(long, value_enum, default_value_t = Overlay::None)]
+  pub overlay: Overlay,
+
+  /// When to use colours
+  #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+  pub color: ColorChoice,
+}
+
+impl RenderArgs {
+  // The input options, in the shape the other commands take them
+  pub fn solve_args(&self) -> SolveArgs {
+    SolveArgs {
+      day: Some(10),
+      part: Some(2),
+      input_args: self.input_args.clone(),
+      ..Default::default()
+    }
+  }
+}
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+  /// Day to export
+  #[arg(
+    short,
+    long,
+    value_parser = PossibleValuesParser::new(["3", "10"]).map(|day| day.parse::<u8>().unwrap())
+  )]
+  pub day: u8,
+
+  #[command(flatten)]
+  pub input_args: InputArgs,
+
+  /// File to write the image to
+  #[arg(short, long)]
+  pub output: PathBuf,
+
+  /// Image format, guessed from the output file's extension if left out
+  #[arg(short, long, value_enum)]
+  pub format: Option<ImageFormat>,
+
+  /// How many pixels wide each cell is drawn, up to 64. Day 10's PPM draws every tile as 3 by 3
+  /// cells, and PPMs over 64 megapixels are refused
+  #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..=64))]
+  pub scale: u16,
+}
+
+impl ExportArgs {
+  // The input options, in the shape the other commands take them
+  pub fn solve_args(&self) -> SolveArgs {
+    SolveArgs {
+      day: Some(self.day),
+      part: Some(2),
+      input_args: self.input_args.clone(),
+      ..Default::default()
+    }
+  }
+}
+
+impl SolveArgs {
+  pub fn parts(&self) -> Vec<Part> {
+    match self.part.and_then(Part::from_number) {
+      Some(part) => vec![part],
+      None => Part::ALL.to_vec(),
+    }
+  }
+}
+
+// The renderer and image writer don't know about clap, so their options are mapped to command
+// line values here
+impl ValueEnum for Charset {
+  fn value_variants<'a>() -> &'a [Self] {
+    &[Charset::Ascii, Charset::Unicode]
+  }
+
+  fn to_possible_value(&self) -> Option<PossibleValue> {
+    Some(match self {
+      Charset::Ascii => PossibleValue::new("ascii"),
+      Charset::Unicode => PossibleValue::new("unicode"),
+    })
+  }
+}
+
+impl ValueEnum for Overlay {
+  fn value_variants<'a>() -> &'a [Self] {
+    &[Overlay::None, Overlay::Enclosed, Overlay::Distances]
+  }
+
+  fn to_possible_value(&self) -> Option<PossibleValue> {
+    Some(match self {
+      Overlay::None => PossibleValue::new("none"),
+      Overlay::Enclosed => PossibleValue::new("enclosed"),
+      Overlay::Distances => PossibleValue::new("distances"),
+    })
+  }
+}
+
+impl ValueEnum for ImageFormat {
+  fn value_variants<'a>() -> &'a [Self] {
+    &[ImageFormat::Ppm, ImageFormat::Svg]
+  }
+
+  fn to_possible_value(&self) -> Option<PossibleValue> {
+    Some(match self {
+      ImageFormat::Ppm => PossibleValue::new("ppm"),
+      ImageFormat::Svg => PossibleValue::new("svg"),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use clap::{CommandFactory, Parser};
+
+  use super::{Cli, ColorChoice, Command, OutputFormat};
+  use crate::day_10::render::{Charset, Overlay};
+  use crate::solver::Part;
+
+  #[test]
+  pub fn cli_definition_is_valid() {
+    Cli::command().debug_assert();
+  }
+
+  #[test]
+  pub fn run_subcommand_parses() {
+    let cli = Cli::parse_from(["aoc_2023", "run", "--day", "5", "--part", "2", "--example"]);
+    match cli.command {
+      Some(Command::Run(args)) => {
+        assert_eq!(args.solve_args.day, Some(5));
+        assert_eq!(args.solve_args.parts(), vec![Part::Two]);
+        assert!(args.solve_args.input_args.example);
+        assert_eq!(args.format, OutputFormat::Text);
+      }
+      other => panic!("unexpected command: {:?}", other),
+    }
+  }
+
+  #[test]
+  pub fn run_format_parses() {
+    let cli = Cli::parse_from(["aoc_2023", "run", "--format", "json"]);
+    match cli.command {
+      Some(Command::Run(args)) => assert_eq!(args.format, OutputFormat::Json),
+      other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(Cli::try_parse_from(["aoc_2023", "run", "--format", "yaml"]).is_err());
+  }
+
+  #[test]
+  pub fn jobs_defaults_to_one_thread_per_cpu() {
+    let jobs = |args: &[&str]| match Cli::parse_from(args).command {
+      Some(Command::Run(args)) => args.jobs,
+      other => panic!("unexpected command: {:?}", other),
+    };
+
+    assert_eq!(jobs(&["aoc_2023", "run"]), None);
+    assert_eq!(jobs(&["aoc_2023", "run", "-j"]), Some(0));
+    assert_eq!(jobs(&["aoc_2023", "run", "--jobs", "4"]), Some(4));
+  }
+
+  #[test]
+  pub fn check_examples_parses() {
+    let cli = Cli::parse_from(["aoc_2023", "check", "--examples", "--day", "3"]);
+    match cli.command {
+      Some(Command::Check(args)) => {
+        assert!(args.examples);
+        assert_eq!(args.solve_args.day, Some(3));
+      }
+      other => panic!("unexpected command: {:?}", other),
+    }
+  }
+
+  #[test]
+  pub fn bench_runs_default_to_ten() {
+    let cli = Cli::parse_from(["aoc_2023", "bench", "--day", "6", "--json"]);
+    match cli.command {
+      Some(Command::Bench(args)) => {
+        assert_eq!(args.runs, 10);
+        assert!(args.json);
+      }
+      other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(Cli::try_parse_from(["aoc_2023", "bench", "--runs", "0"]).is_err());
+  }
+
+  #[test]
+  pub fn render_defaults_to_unicode_without_an_overlay() {
+    let cli = Cli::parse_from(["aoc_2023", "render", "--example", "--color", "never"]);
+    match cli.command {
+      Some(Command::Render(args)) => {
+        assert_eq!(args.charset, Charset::Unicode);
+        assert_eq!(args.overlay, Overlay::None);
+        assert_eq!(args.color, ColorChoice::Never);
+        assert_eq!(args.solve_args().day, Some(10));
+      }
+      other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(Cli::try_parse_from(["aoc_2023", "render", "--overlay", "heat"]).is_err());
+  }
+
+  #[test]
+  pub fn export_requires_a_day_and_an_output() {
+    let cli = Cli::parse_from(["aoc_2023", "export", "-d", "3", "-o", "day_3.svg"]);
+    match cli.command {
+      Some(Command::Export(args)) => {
+        assert_eq!(args.day, 3);
+        assert_eq!(args.format, None);
+        assert_eq!(args.scale, 4);
+      }
+      other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(Cli::try_parse_from(["aoc_2023", "export", "-o", "day_3.svg"]).is_err());
+    assert!(Cli::try_parse_from(["aoc_2023", "export", "-d", "3"]).is_err());
+    assert!(Cli::try_parse_from(["aoc_2023", "export", "-d", "5", "-o", "day_5.svg"]).is_err());
+    assert!(Cli::try_parse_from([
+      "aoc_2023", "export", "-d", "10", "-o", "a.ppm", "--scale", "64"
+    ])
+    .is_ok());
+    assert!(Cli::try_parse_from([
+      "aoc_2023", "export", "-d", "10", "-o", "a.ppm", "--scale", "65"
+    ])
+    .is_err());
+  }
+
+  #[test]
+  pub fn log_flags_are_global() {
+    let cli = Cli::parse_from(["aoc_2023", "run", "-vv", "--log", "day_7=debug"]);
+    assert_eq!(cli.log_args.verbose, 2);
+    assert_eq!(cli.log_args.target_filters.len(), 1);
+    assert!(Cli::try_parse_from(["aoc_2023", "-v", "-q"]).is_err());
+  }
+
+  #[test]
+  pub fn input_requires_day() {
+    assert!(Cli::try_parse_from(["aoc_2023", "run", "--input", "input.txt"]).is_err());
+  }
+}
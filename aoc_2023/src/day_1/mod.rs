@@ -1,6 +1,25 @@
 use std::collections::HashMap;
-
-const INPUT_FILENAME: &str = "./src/day_1/input.txt";
+use std::io::BufRead;
+
+use crate::parse::{InputLine, LineReader, Lines, ParseError};
+use crate::solver::{Answer, Example, Part, PartResult, Solver};
+
+const DAY: u8 = 1;
+
+const EXAMPLES: &[Example] = &[
+  Example {
+    name: "part_1_example_input.txt",
+    input: include_str!("part_1_example_input.txt"),
+    part_1: Some("142"),
+    part_2: None,
+  },
+  Example {
+    name: "part_2_example_input.txt",
+    input: include_str!("part_2_example_input.txt"),
+    part_1: None,
+    part_2: Some("281"),
+  },
+];
 
 fn get_digit_word_map() -> HashMap<&'static str, usize> {
   HashMap::from([
@@ -21,7 +40,7 @@ fn parse_slice_as_uint(slice: &str) -> Result<usize, ()> {
   // Attempt to parse first char as digit
   let first_char_string = slice_as_string
     .chars()
-    .nth(0)
+    .next()
     .unwrap()
     .to_string();
 
@@ -35,17 +54,13 @@ fn finding_matching_digit_word_in_slice(slice: &str) -> Option<usize> {
   let digit_map = get_digit_word_map();
 
   let matching_key = digit_map.keys().find(|key| slice == **key);
-  if let Some(key) = matching_key {
-    return Some(*digit_map.get(key).unwrap());
-  } else {
-    None
-  }
+  matching_key.map(|key| *digit_map.get(key).unwrap())
 }
 
-fn part_1_process_line(line: &str) -> u32 {
+fn part_1_process_line(line: &InputLine) -> Result<u32, ParseError> {
   let numbers: Vec<u32> = line
+    .text
     .chars()
-    .into_iter()
     .fold(Vec::new(), |mut acc, char| {
       match char.to_string().parse::<u32>() {
         Ok(char_string_as_uint) => {
@@ -67,13 +82,9 @@ fn part_1_process_line(line: &str) -> u32 {
     _ => None,
   };
 
-  if let Some((first, last)) = first_and_last {
-    let result_as_string = format!("{}{}", first, last);
-
-    // I can probably assume unwrapping is okay here
-    result_as_string.parse::<u32>().unwrap()
-  } else {
-    0
+  match first_and_last {
+    Some((first, last)) => Ok(first * 10 + last),
+    None => Err(line.error(line.text, "expected at least one digit")),
   }
 }
 
@@ -91,14 +102,24 @@ fn find_digit_in_slices(slices: Vec<&str>) -> Option<usize> {
   None
 }
 
-fn part_2_process_line(line: &str) -> u32 {
+fn part_2_process_line(input_line: &InputLine) -> Result<u32, ParseError> {
+  let line = input_line.text;
+
+  // Byte offsets of every character plus the end of the line, so that slices never split a
+  // character
+  let boundaries = line
+    .char_indices()
+    .map(|(index, _)| index)
+    .chain(std::iter::once(line.len()))
+    .collect::<Vec<_>>();
+
   // Search for first number
   let first = {
-    let forward_slices = (0..line.len())
+    let forward_slices = (0..boundaries.len())
       .flat_map(|first_index| {
-        (first_index + 1..line.len() + 1).map(move |last_index| (first_index, last_index))
+        (first_index + 1..boundaries.len()).map(move |last_index| (first_index, last_index))
       })
-      .map(|(first_index, last_index)| &line[first_index..last_index])
+      .map(|(first_index, last_index)| &line[boundaries[first_index]..boundaries[last_index]])
       .collect::<Vec<_>>();
 
     find_digit_in_slices(forward_slices)
@@ -106,93 +127,94 @@ fn part_2_process_line(line: &str) -> u32 {
 
   // Search for the last number
   let last = {
-    let backward_slices = (0..line.len())
+    let backward_slices = (0..boundaries.len())
       .rev()
       .flat_map(|first_index| {
-        (first_index + 1..line.len() + 1)
+        (first_index + 1..boundaries.len())
           .rev()
           .map(|last_index| (first_index, last_index))
           .collect::<Vec<_>>()
       })
-      .map(|(first, last)| &line[first..last])
+      .map(|(first, last)| &line[boundaries[first]..boundaries[last]])
       .collect::<Vec<_>>();
 
     find_digit_in_slices(backward_slices)
   };
 
   // Combine first + last
-  if first != None && last != None {
-    let result_as_string = format!("{}{}", first.unwrap(), last.unwrap());
-
-    // I can probably assume unwrapping is okay here
-    result_as_string.parse::<u32>().unwrap()
-  } else {
-    0
+  match (first, last) {
+    (Some(first), Some(last)) => Ok((first * 10 + last) as u32),
+    _ => Err(input_line.error(line, "expected at least one digit or digit word")),
   }
 }
 
-fn part_1(contents: &str) -> u32 {
-  contents
-    .lines()
-    .into_iter()
-    .fold(0, |acc, line| acc + part_1_process_line(&line))
+fn part_1(lines: impl Lines) -> Result<u32, ParseError> {
+  lines.try_fold_lines(DAY, 0, |acc, line| Ok(acc + part_1_process_line(&line)?))
 }
 
-fn part_2(contents: &str) -> u32 {
-  contents
-    .lines()
-    .into_iter()
-    .fold(0, |acc, line| acc + part_2_process_line(&line))
+fn part_2(lines: impl Lines) -> Result<u32, ParseError> {
+  lines.try_fold_lines(DAY, 0, |acc, line| Ok(acc + part_2_process_line(&line)?))
 }
 
-pub fn run() -> Result<(), std::io::Error> {
-  println!("running day 1");
-  println!("reading contents of {}", INPUT_FILENAME);
-  let contents = crate::utils::read_input(INPUT_FILENAME)?;
+pub struct Day1;
+
+impl Solver for Day1 {
+  fn day(&self) -> u8 {
+    DAY
+  }
+
+  fn title(&self) -> &'static str {
+    "Trebuchet?!"
+  }
+
+  fn examples(&self) -> &'static [Example] {
+    EXAMPLES
+  }
 
-  let result = part_1(&contents);
-  println!("part one result: {}", result);
+  fn part_1(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_1(input)?)))
+  }
 
-  let result = part_2(&contents);
-  println!("part two result: {}", result);
+  fn part_2(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_2(input)?)))
+  }
 
-  Ok(())
+  fn solve_lines(&self, part: Part, reader: &mut dyn BufRead) -> PartResult {
+    let lines = LineReader::new(reader);
+    let result = match part {
+      Part::One => part_1(lines)?,
+      Part::Two => part_2(lines)?,
+    };
+    Ok(Some(Answer::from(result)))
+  }
 }
 
 #[cfg(test)]
 mod tests {
-  use crate::utils::read_input;
+  use crate::utils::read_day_input;
 
-  use super::{part_1, part_2, INPUT_FILENAME};
+  use super::{part_1, part_2};
 
-  const PART_1_EXAMPLE_INPUT_FILENAME: &str = "./src/day_1/part_1_example_input.txt";
-  const PART_2_EXAMPLE_INPUT_FILENAME: &str = "./src/day_1/part_2_example_input.txt";
+  const PART_1_EXAMPLE_INPUT_FILENAME: &str = "part_1_example_input.txt";
+  const PART_2_EXAMPLE_INPUT_FILENAME: &str = "part_2_example_input.txt";
 
   #[test]
-  pub fn day_1_part_1_example_works() {
-    let contents = read_input(PART_1_EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 142);
+  pub fn day_1_non_ascii_characters_are_skipped() {
+    assert_eq!(part_1("1é2").unwrap(), 12);
+    assert_eq!(part_2("1é2\nétwoxé").unwrap(), 12 + 22);
   }
 
   #[test]
-  pub fn day_1_part_1_solution_works() {
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 55123);
+  pub fn day_1_part_1_example_works() {
+    let contents = read_day_input(1, PART_1_EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_1(&contents).unwrap();
+    assert_eq!(result, 142);
   }
 
   #[test]
   pub fn day_1_part_2_example_works() {
-    let contents = read_input(PART_2_EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
+    let contents = read_day_input(1, PART_2_EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_2(&contents).unwrap();
     assert_eq!(result, 281);
   }
-
-  #[test]
-  pub fn day_1_part_2_solution_works() {
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
-    assert_eq!(result, 55260);
-  }
 }
@@ -0,0 +1,148 @@
+use std::io::Error;
+
+use super::{pipe_connections, PipeMap};
+use crate::grid::{Grid, Position};
+use crate::image::{ppm, ImageFormat, Rgb, Svg};
+
+const GROUND: Rgb = Rgb(24, 24, 32);
+const ENCLOSED: Rgb = Rgb(60, 110, 200);
+const LOOP: Rgb = Rgb(250, 200, 60);
+const JUNK: Rgb = Rgb(70, 70, 80);
+const START: Rgb = Rgb(240, 90, 60);
+
+// Each tile is drawn as 3x3 pixels, enough to show which sides a pipe leads out of
+const TILE_SIZE: usize = 3;
+
+// Draws a pipe into its tile's pixels as a centre with an arm towards each side it leads out of
+fn draw_pipe(pixels: &mut Grid<Rgb>, coordinates: Position, char: char, color: Rgb) {
+  let Some(connections) = pipe_connections(char) else {
+    return;
+  };
+
+  let centre = Position::new(coordinates.x * TILE_SIZE + 1, coordinates.y * TILE_SIZE + 1);
+  pixels[centre] = color;
+
+  for (dx, dy) in connections {
+    if let Some(arm) = pixels.offset(centre, dx, dy) {
+      pixels[arm] = color;
+    }
+  }
+}
+
+fn export_ppm(pipe_map: &PipeMap, scale: usize) -> Result<Vec<u8>, Error> {
+  let pipes = pipe_map.pipes();
+  let mut pixels = Grid::filled(
+    pipes.width() * TILE_SIZE,
+    pipes.height() * TILE_SIZE,
+    GROUND,
+  );
+
+  for (coordinates, enclosed) in pipe_map.enclosed_tiles().iter() {
+    if *enclosed {
+      for y in 0..TILE_SIZE {
+        for x in 0..TILE_SIZE {
+          let pixel = Position::new(coordinates.x * TILE_SIZE + x, coordinates.y * TILE_SIZE + y);
+          pixels[pixel] = ENCLOSED;
+        }
+      }
+    }
+  }
+
+  for (coordinates, char) in pipes.iter() {
+    let color = if coordinates == pipe_map.starting_coordinates() {
+      START
+    } else if pipe_map.is_on_loop(coordinates) {
+      LOOP
+    } else {
+      JUNK
+    };
+
+    draw_pipe(&mut pixels, coordinates, *char, color);
+  }
+
+  ppm(&pixels, scale)
+}
+
+fn export_svg(pipe_map: &PipeMap, scale: usize) -> Vec<u8> {
+  let pipes = pipe_map.pipes();
+  let mut svg = Svg::new(pipes.width(), pipes.height(), scale, GROUND);
+
+  // Runs of enclosed tiles along each row are drawn as one box
+  let enclosed = pipe_map.enclosed_tiles();
+  for (y, row) in enclosed.rows().enumerate() {
+    let mut run_start = None;
+
+    for (x, enclosed) in row.iter().chain([&false]).enumerate() {
+      match (enclosed, run_start) {
+        (true, None) => run_start = Some(x),
+        (false, Some(start)) => {
+          svg.rect(start as f64, y as f64, (x - start) as f64, 1.0, ENCLOSED);
+          run_start = None;
+        }
+        _ => {}
+      }
+    }
+  }
+
+  let centres = pipe_map
+    .pipe_loop()
+    .iter()
+    .map(|coordinates| (coordinates.x as f64 + 0.5, coordinates.y as f64 + 0.5))
+    .collect::<Vec<_>>();
+  svg.polygon(&centres, LOOP, 0.4);
+
+  let start = pipe_map.starting_coordinates();
+  svg.circle(start.x as f64 + 0.5, start.y as f64 + 0.5, 0.4, START);
+
+  svg.finish().into_bytes()
+}
+
+// The loop, the pipes that aren't part of it and the tiles it encloses. SVG leaves out the junk
+// pipes and traces the loop as a single outline. Err if the image would be too large
+pub fn export(pipe_map: &PipeMap, format: ImageFormat, scale: usize) -> Result<Vec<u8>, Error> {
+  match format {
+    ImageFormat::Ppm => export_ppm(pipe_map, scale),
+    ImageFormat::Svg => Ok(export_svg(pipe_map, scale)),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{export, ENCLOSED, GROUND, JUNK, LOOP, START};
+  use crate::day_10::PipeMap;
+  use crate::image::test_support::pixel;
+  use crate::image::ImageFormat;
+
+  // A loop around a single enclosed tile, with a junk pipe in the corner
+  const PIPE_MAP: &str = "....|\n.S-7.\n.|.|.\n.L-J.\n.....";
+
+  #[test]
+  pub fn day_10_ppm_draws_pipes_and_the_enclosed_region() {
+    let pipe_map = PipeMap::from_input(PIPE_MAP).unwrap();
+    let image = export(&pipe_map, ImageFormat::Ppm, 1).unwrap();
+
+    assert!(image.starts_with(b"P6\n15 15\n255\n"));
+    // The start is an F, leading east and south
+    assert_eq!(pixel(&image, 15, 15, 4, 4), START);
+    assert_eq!(pixel(&image, 15, 15, 5, 4), START);
+    assert_eq!(pixel(&image, 15, 15, 4, 5), START);
+    assert_eq!(pixel(&image, 15, 15, 4, 3), GROUND);
+    assert_eq!(pixel(&image, 15, 15, 7, 4), LOOP);
+    assert_eq!(pixel(&image, 15, 15, 6, 6), ENCLOSED);
+    assert_eq!(pixel(&image, 15, 15, 13, 0), JUNK);
+  }
+
+  #[test]
+  pub fn day_10_svg_traces_the_loop_in_order() {
+    let pipe_map = PipeMap::from_input(PIPE_MAP).unwrap();
+    let image = String::from_utf8(export(&pipe_map, ImageFormat::Svg, 10).unwrap()).unwrap();
+
+    assert!(
+      image.contains("points=\"1.5,1.5 2.5,1.5 3.5,1.5 3.5,2.5 3.5,3.5 2.5,3.5 1.5,3.5 1.5,2.5\"")
+    );
+    assert!(image.contains(&format!(
+      "<rect x=\"2\" y=\"2\" width=\"1\" height=\"1\" fill=\"{}\"/>",
+      ENCLOSED.hex()
+    )));
+  }
+}
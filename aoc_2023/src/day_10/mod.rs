@@ -1,284 +1,494 @@
-use std::collections::VecDeque;
-
-use lazy_static::lazy_static;
-
-const INPUT_FILENAME: &str = "./src/day_10/input.txt";
+use self::render::{render, Overlay, RenderOptions};
+use crate::grid::{Grid, Position, EAST, NORTH, SOUTH, WEST};
+use crate::parse::{end_of_input_error, input_lines, ParseError};
+use crate::solver::{Answer, Example, Part, PartResult, Solver};
+
+pub mod export;
+pub mod render;
+
+const DAY: u8 = 10;
+
+const EXAMPLES: &[Example] = &[
+  Example {
+    name: "example_input_1.txt",
+    input: include_str!("example_input_1.txt"),
+    part_1: Some("4"),
+    part_2: None,
+  },
+  Example {
+    name: "example_input_2.txt",
+    input: include_str!("example_input_2.txt"),
+    part_1: Some("8"),
+    part_2: None,
+  },
+  Example {
+    name: "example_input_3.txt",
+    input: include_str!("example_input_3.txt"),
+    part_1: None,
+    part_2: Some("4"),
+  },
+  Example {
+    name: "example_input_4.txt",
+    input: include_str!("example_input_4.txt"),
+    part_1: None,
+    part_2: Some("4"),
+  },
+  Example {
+    name: "example_input_5.txt",
+    input: include_str!("example_input_5.txt"),
+    part_1: None,
+    part_2: Some("8"),
+  },
+  Example {
+    name: "example_input_6.txt",
+    input: include_str!("example_input_6.txt"),
+    part_1: None,
+    part_2: Some("10"),
+  },
+];
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PipePiece {
+  pub coordinates: Position,
+  pub char: char,
+}
 
-lazy_static! {
-  static ref VALID_NEXT_PIPES_FROM_STARTING_POINT: [(RelativeCoordinates, Vec<char>); 4] = [
-    (RelativeCoordinates::north(), vec!['|', 'F', '7']),
-    (RelativeCoordinates::east(), vec!['-', '7', 'J']),
-    (RelativeCoordinates::south(), vec!['|', 'J', 'L']),
-    (RelativeCoordinates::west(), vec!['-', 'F', 'L']),
-  ];
+// The two directions a pipe leads in. Ground leads nowhere
+fn pipe_connections(char: char) -> Option<[(isize, isize); 2]> {
+  match char {
+    '|' => Some([NORTH, SOUTH]),
+    '-' => Some([WEST, EAST]),
+    'L' => Some([NORTH, EAST]),
+    'J' => Some([NORTH, WEST]),
+    '7' => Some([WEST, SOUTH]),
+    'F' => Some([EAST, SOUTH]),
+    // realistically will only be '.' - ground
+    _ => None,
+  }
 }
 
-#[derive(Debug, PartialEq, Clone)]
-struct Coordinates {
-  column: usize,
-  row: usize,
+// Where both ends of a pipe lead if it were at `coordinates`, None for an end running off the map
+fn pipe_ends(
+  pipes: &Grid<char>,
+  coordinates: Position,
+  pipe: char,
+) -> Option<[Option<Position>; 2]> {
+  let connections = pipe_connections(pipe)?;
+
+  Some(connections.map(|(dx, dy)| pipes.offset(coordinates, dx, dy)))
 }
 
-impl Coordinates {
-  fn from_index(column_count: &usize, index: &usize) -> Coordinates {
-    let row = index / column_count;
-    let column = index % column_count;
+fn connected_coordinates(pipes: &Grid<char>, coordinates: Position) -> Option<[Position; 2]> {
+  let [first, second] = pipe_ends(pipes, coordinates, pipes[coordinates])?;
 
-    Coordinates { row, column }
-  }
+  Some([first?, second?])
+}
 
-  fn to_index(&self, column_count: &usize) -> usize {
-    (self.row * column_count) + self.column
+// The pipe hidden under 'S', worked out from which of its neighbours lead back to it. Exactly two
+// have to, otherwise there's either no loop through the start or more than one way around it
+fn resolve_starting_pipe(
+  pipes: &Grid<char>,
+  starting_coordinates: Position,
+) -> Result<char, String> {
+  let connected = pipes
+    .neighbours_4(starting_coordinates)
+    .filter(|neighbour| {
+      pipe_ends(pipes, *neighbour, pipes[*neighbour])
+        .is_some_and(|ends| ends.contains(&Some(starting_coordinates)))
+    })
+    .collect::<Vec<_>>();
+
+  match connected[..] {
+    // Any two of the four neighbours are joined by exactly one pipe
+    [first, second] => Ok(
+      "|-LJ7F"
+        .chars()
+        .find(|pipe| {
+          pipe_ends(pipes, starting_coordinates, *pipe)
+            .is_some_and(|ends| ends.contains(&Some(first)) && ends.contains(&Some(second)))
+        })
+        .unwrap(),
+    ),
+    [] | [_] => Err(format!(
+      "expected two pipes to connect to 'S', found {}",
+      connected.len()
+    )),
+    _ => Err(format!(
+      "expected 'S' to be a single pipe, but {} pipes connect to it",
+      connected.len()
+    )),
   }
 }
 
-#[derive(Debug, Clone)]
-struct RelativeCoordinates {
-  column: isize,
-  row: isize,
-}
+// Follows the pipes from the start until they lead back to it, marking every tile on the way.
+// Every pipe only has the one way on, so this visits each tile of the loop once. Err is the first
+// tile that doesn't carry on the loop: ground, a pipe that doesn't lead back the way the walk came
+// or one leading off the map
+fn walk_pipe_loop(
+  pipes: &Grid<char>,
+  starting_coordinates: Position,
+) -> Result<(Vec<Position>, Grid<bool>), Position> {
+  let mut pipe_loop = vec![starting_coordinates];
+  let mut on_loop = Grid::filled(pipes.width(), pipes.height(), false);
+  on_loop[starting_coordinates] = true;
+
+  // The start has already been resolved to a pipe whose neighbours lead back to it
+  let [mut current, _] = connected_coordinates(pipes, starting_coordinates).unwrap();
+  let mut previous = starting_coordinates;
+
+  while current != starting_coordinates {
+    if on_loop[current] {
+      return Err(current);
+    }
 
-impl RelativeCoordinates {
-  fn north() -> RelativeCoordinates {
-    RelativeCoordinates { column: 0, row: -1 }
-  }
+    pipe_loop.push(current);
+    on_loop[current] = true;
 
-  fn south() -> RelativeCoordinates {
-    RelativeCoordinates { column: 0, row: 1 }
-  }
+    let next = match connected_coordinates(pipes, current).ok_or(current)? {
+      [first, second] if first == previous => second,
+      [first, second] if second == previous => first,
+      _ => return Err(current),
+    };
 
-  fn east() -> RelativeCoordinates {
-    RelativeCoordinates { column: 1, row: 0 }
+    previous = current;
+    current = next;
   }
 
-  fn west() -> RelativeCoordinates {
-    RelativeCoordinates { column: -1, row: 0 }
-  }
+  Ok((pipe_loop, on_loop))
 }
 
-#[derive(Debug, PartialEq)]
-struct PipePiece {
-  coordinates: Coordinates,
-  char: char,
+#[derive(Debug)]
+pub struct PipeMap {
+  pipes: Grid<char>,
+  starting_coordinates: Position,
+  // Tiles of the loop in order, starting from and going around back to the start
+  pipe_loop: Vec<Position>,
+  on_loop: Grid<bool>,
 }
 
-#[derive(Debug)]
-struct PipeMap {
-  pipes: Vec<PipePiece>,
-  column_count: usize,
-  row_count: usize,
-  starting_coordinates: Coordinates,
+// Points at a tile of the map in the input it was parsed from
+fn error_at(input: &str, coordinates: Position, message: impl Into<String>) -> ParseError {
+  // The grid came from these lines, so the tile is on one of them
+  let line = input_lines(DAY, input)
+    .nth(coordinates.y)
+    .unwrap();
+  let (index, char) = line
+    .text
+    .char_indices()
+    .nth(coordinates.x)
+    .unwrap();
+
+  line.error(&line.text[index..index + char.len_utf8()], message)
 }
 
 impl PipeMap {
-  fn from_input(input: &str) -> PipeMap {
-    let lines = input.split('\n').collect::<Vec<_>>();
+  pub fn from_input(input: &str) -> Result<PipeMap, ParseError> {
+    let mut pipes = Grid::parse(DAY, input, "a pipe, ground or 'S'", |char| {
+      "|-LJ7F.S".contains(char).then_some(char)
+    })?;
 
-    println!("lines");
-
-    let pipes = lines
-      .iter()
-      .enumerate()
-      .flat_map(|(row, line)| {
-        line
-          .chars()
-          .enumerate()
-          .into_iter()
-          .map(move |(column, char)| {
-            let coordinates = Coordinates { column, row };
-            //
-            PipePiece { coordinates, char }
-          })
-      })
-      .collect::<Vec<_>>();
+    if pipes.is_empty() {
+      return Err(end_of_input_error(DAY, input, "expected a row of pipes"));
+    }
 
-    let row_count = lines.len();
-    let column_count = lines[0].len();
+    let starting_coordinates = pipes
+      .find(|char| *char == 'S')
+      .ok_or_else(|| end_of_input_error(DAY, input, "expected a starting position 'S'"))?;
 
-    let s_index = pipes
-      .iter()
-      .enumerate()
-      .find_map(|(index, pipe)| if pipe.char == 'S' { Some(index) } else { None })
-      .unwrap();
+    let starting_pipe = resolve_starting_pipe(&pipes, starting_coordinates)
+      .map_err(|message| error_at(input, starting_coordinates, message))?;
+    pipes[starting_coordinates] = starting_pipe;
 
-    let starting_coordinates = Coordinates::from_index(&column_count, &s_index);
+    let (pipe_loop, on_loop) =
+      walk_pipe_loop(&pipes, starting_coordinates).map_err(|coordinates| {
+        error_at(
+          input,
+          coordinates,
+          "expected a pipe carrying on the loop from 'S'",
+        )
+      })?;
 
-    PipeMap {
+    Ok(PipeMap {
       pipes,
-      row_count,
-      column_count,
       starting_coordinates,
-    }
+      pipe_loop,
+      on_loop,
+    })
   }
 
-  fn get_pipe_piece_at_coordinates(&self, coordinates: &Coordinates) -> &PipePiece {
-    let index = coordinates.to_index(&self.column_count);
-    &self.pipes[index]
-  }
-
-  fn build_pipe_loop_with_distances(&self) -> Vec<(u32, &PipePiece)> {
-    let mut result: Vec<(u32, &PipePiece)> = Vec::new();
-    let mut queue: VecDeque<(u32, &PipePiece)> = VecDeque::new();
-
-    let starting_coordinates = self.starting_coordinates.clone();
-    let starting_pipe_piece = self.get_pipe_piece_at_coordinates(&starting_coordinates);
-    queue.push_back((0, starting_pipe_piece));
-
-    while let Some((current_pipe_piece_distance, current_pipe_piece)) = queue.pop_front() {
-      let next_relative_coords: Vec<RelativeCoordinates> = match current_pipe_piece.char {
-        '|' => vec![RelativeCoordinates::north(), RelativeCoordinates::south()],
-        '-' => vec![RelativeCoordinates::west(), RelativeCoordinates::east()],
-        'L' => vec![RelativeCoordinates::north(), RelativeCoordinates::east()],
-        'J' => vec![RelativeCoordinates::north(), RelativeCoordinates::west()],
-        '7' => vec![RelativeCoordinates::west(), RelativeCoordinates::south()],
-        'F' => vec![RelativeCoordinates::east(), RelativeCoordinates::south()],
-        'S' => {
-          // Make sure the pipes can actually connect properly
-          VALID_NEXT_PIPES_FROM_STARTING_POINT
-            .clone()
-            .into_iter()
-            .fold(Vec::new(), |mut acc, (relative_coords, valid_chars)| {
-              if let Ok(coords) =
-                self.try_add_relative_coordinates(&current_pipe_piece.coordinates, &relative_coords)
-              {
-                let pipe_piece_at_coords = self.get_pipe_piece_at_coordinates(&coords);
-
-                if valid_chars.contains(&pipe_piece_at_coords.char) {
-                  acc.push(relative_coords)
-                }
-              };
-
-              acc
-            })
-        }
-        // realistically will only be '.' - ground
-        _ => Vec::new(),
-      };
-
-      let next_coordinates = next_relative_coords
-        .into_iter()
-        // attempt to add coordinates together
-        .map(|relative_coordinates| {
-          self.try_add_relative_coordinates(&current_pipe_piece.coordinates, &relative_coordinates)
-        })
-        .filter(|addition_result| addition_result.is_ok())
-        .map(|addition_result| addition_result.unwrap())
-        // Filter out next coordinates that have already been logged
-        .filter(|coordinates| {
-          let pipe_piece = self.get_pipe_piece_at_coordinates(&coordinates);
-
-          result
-            .iter()
-            .find(|(_, other_pipe_piece)| other_pipe_piece.coordinates == pipe_piece.coordinates)
-            .is_none()
-        })
-        .collect::<Vec<_>>();
+  // The map with 'S' replaced by the pipe underneath it
+  pub fn pipes(&self) -> &Grid<char> {
+    &self.pipes
+  }
 
-      result.push((current_pipe_piece_distance, current_pipe_piece));
+  pub fn starting_coordinates(&self) -> Position {
+    self.starting_coordinates
+  }
 
-      for coordinates in next_coordinates.into_iter() {
-        let next_pipe_piece = self.get_pipe_piece_at_coordinates(&coordinates);
-        queue.push_back((current_pipe_piece_distance + 1, next_pipe_piece));
-      }
+  pub fn starting_pipe(&self) -> char {
+    self.pipes[self.starting_coordinates]
+  }
+
+  fn get_pipe_piece_at_coordinates(&self, coordinates: Position) -> PipePiece {
+    PipePiece {
+      coordinates,
+      char: self.pipes[coordinates],
     }
+  }
 
-    result
+  // The loop as an ordered cycle, starting from the start. The last tile leads back to the first
+  pub fn pipe_loop(&self) -> &[Position] {
+    &self.pipe_loop
   }
 
-  fn try_add_relative_coordinates(
-    &self,
-    coordinates: &Coordinates,
-    coordinates_to_add: &RelativeCoordinates,
-  ) -> Result<Coordinates, ()> {
-    let added = RelativeCoordinates {
-      column: coordinates.column as isize + coordinates_to_add.column,
-      row: coordinates.row as isize + coordinates_to_add.row,
-    };
+  pub fn is_on_loop(&self, coordinates: Position) -> bool {
+    self.on_loop[coordinates]
+  }
+
+  // Every pipe of the loop in order, with how far it is from the start going whichever way
+  // around is shorter
+  pub fn build_pipe_loop_with_distances(&self) -> Vec<(u32, PipePiece)> {
+    let length = self.pipe_loop.len();
 
-    // Make sure the coordinates are within the bounds of the grid
-    if added.row >= 0
-      && added.row <= (self.row_count - 1) as isize
-      && added.column >= 0
-      && added.column <= (self.column_count - 1) as isize
-    {
-      Ok(Coordinates {
-        column: added.column as usize,
-        row: added.row as usize,
+    self
+      .pipe_loop
+      .iter()
+      .enumerate()
+      .map(|(index, coordinates)| {
+        let distance = std::cmp::min(index, length - index) as u32;
+        (distance, self.get_pipe_piece_at_coordinates(*coordinates))
       })
-    } else {
-      Err(())
+      .collect()
+  }
+
+  // Which tiles are inside the loop without being part of it. Scanning each row from the left, a
+  // tile is inside once an odd number of loop pipes reaching north have been crossed. Runs along
+  // the loop like L-7 count once and L-J cancel out, which is what lets tiles squeeze between
+  // pipes
+  pub fn enclosed_tiles(&self) -> Grid<bool> {
+    let mut enclosed = Grid::filled(self.pipes.width(), self.pipes.height(), false);
+
+    for (y, row) in self.pipes.rows().enumerate() {
+      let mut inside = false;
+
+      for (x, char) in row.iter().enumerate() {
+        let coordinates = Position::new(x, y);
+
+        if !self.on_loop[coordinates] {
+          enclosed[coordinates] = inside;
+        } else if matches!(*char, '|' | 'L' | 'J') {
+          inside = !inside;
+        }
+      }
     }
+
+    enclosed
+  }
+
+  pub fn count_enclosed_tiles(&self) -> usize {
+    self
+      .enclosed_tiles()
+      .iter()
+      .filter(|(_, enclosed)| **enclosed)
+      .count()
   }
 }
 
-fn part_1(input: &str) -> u32 {
-  let pipe_map = PipeMap::from_input(input);
+fn part_1(input: &str) -> Result<u32, ParseError> {
+  let pipe_map = PipeMap::from_input(input)?;
 
   let loop_with_distances = pipe_map.build_pipe_loop_with_distances();
-  println!("pipe_loop");
-  for (distance, pipe) in loop_with_distances.iter() {
-    println!("distance: {}, pipe: {:?}", distance, pipe);
+  log::debug!("pipe_loop has {} pipes", loop_with_distances.len());
+  if log::log_enabled!(log::Level::Trace) {
+    let options = RenderOptions {
+      overlay: Overlay::Distances,
+      ..Default::default()
+    };
+    log::trace!("distances:\n{}", render(&pipe_map, &options));
   }
 
-  loop_with_distances
-    .into_iter()
-    .fold(0, |acc, (distance, _)| std::cmp::max(acc, distance))
+  Ok(
+    loop_with_distances
+      .into_iter()
+      .fold(0, |acc, (distance, _)| std::cmp::max(acc, distance)),
+  )
 }
 
-pub fn run() -> Result<(), std::io::Error> {
-  println!("running day 10");
-  let input = crate::utils::read_input(INPUT_FILENAME)?;
+fn part_2(input: &str) -> Result<usize, ParseError> {
+  let pipe_map = PipeMap::from_input(input)?;
+  log::debug!("starting pipe is {}", pipe_map.starting_pipe());
+
+  Ok(pipe_map.count_enclosed_tiles())
+}
 
-  let part_1_total = part_1(&input);
-  println!("part_1 total {}", part_1_total);
+pub struct Day10;
 
-  // let part_2_total = part_2(&input);
-  // println!("part_2 total {}", part_2_total);
+impl Solver for Day10 {
+  fn day(&self) -> u8 {
+    DAY
+  }
 
-  Ok(())
+  fn title(&self) -> &'static str {
+    "Pipe Maze"
+  }
+
+  fn examples(&self) -> &'static [Example] {
+    EXAMPLES
+  }
+
+  fn part_1(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_1(input)?)))
+  }
+
+  fn part_2(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_2(input)?)))
+  }
+
+  fn parse(&self, _part: Part, input: &str) -> Option<Result<(), ParseError>> {
+    Some(PipeMap::from_input(input).map(|_| ()))
+  }
 }
 
 #[cfg(test)]
 mod tests {
-  use crate::utils::read_input;
+  use crate::utils::read_day_input;
 
-  use super::{part_1, INPUT_FILENAME};
+  use super::{part_1, part_2, PipeMap};
+  use crate::grid::Position;
 
-  const EXAMPLE_INPUT_1: &str = "./src/day_10/example_input_1.txt";
-  const EXAMPLE_INPUT_2: &str = "./src/day_10/example_input_2.txt";
+  const EXAMPLE_INPUT_1: &str = "example_input_1.txt";
+  const EXAMPLE_INPUT_2: &str = "example_input_2.txt";
+  const EXAMPLE_INPUT_3: &str = "example_input_3.txt";
+  const EXAMPLE_INPUT_4: &str = "example_input_4.txt";
+  const EXAMPLE_INPUT_5: &str = "example_input_5.txt";
+  const EXAMPLE_INPUT_6: &str = "example_input_6.txt";
+
+  #[test]
+  pub fn day_10_missing_start_is_reported() {
+    let error = part_1(".....\n.F-7.\n.|.|.\n.L-J.\n.....").unwrap_err();
+    assert_eq!(error.line, 6);
+    assert_eq!(error.message, "expected a starting position 'S'");
+  }
+
+  #[test]
+  pub fn day_10_unknown_tile_is_reported() {
+    let error = part_1(".....\n.S-7.\n.|x|.\n.L-J.\n.....").unwrap_err();
+    assert_eq!((error.line, error.column), (3, 3));
+  }
 
   #[test]
   pub fn day_10_part_1_example_1_works() {
-    let contents = read_input(EXAMPLE_INPUT_1).unwrap();
-    let result = part_1(&contents);
+    let contents = read_day_input(10, EXAMPLE_INPUT_1).unwrap();
+    let result = part_1(&contents).unwrap();
     assert_eq!(result, 4);
   }
 
   #[test]
   pub fn day_10_part_1_example_2_works() {
-    let contents = read_input(EXAMPLE_INPUT_2).unwrap();
-    let result = part_1(&contents);
+    let contents = read_day_input(10, EXAMPLE_INPUT_2).unwrap();
+    let result = part_1(&contents).unwrap();
+    assert_eq!(result, 8);
+  }
+
+  #[test]
+  pub fn day_10_part_2_example_3_works() {
+    let contents = read_day_input(10, EXAMPLE_INPUT_3).unwrap();
+    let result = part_2(&contents).unwrap();
+    assert_eq!(result, 4);
+  }
+
+  #[test]
+  pub fn day_10_part_2_example_4_works() {
+    let contents = read_day_input(10, EXAMPLE_INPUT_4).unwrap();
+    let result = part_2(&contents).unwrap();
+    assert_eq!(result, 4);
+  }
+
+  #[test]
+  pub fn day_10_part_2_example_5_works() {
+    let contents = read_day_input(10, EXAMPLE_INPUT_5).unwrap();
+    let result = part_2(&contents).unwrap();
     assert_eq!(result, 8);
   }
 
   #[test]
-  pub fn day_10_part_1_solution_works() {
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 6786);
-  }
-
-  // #[test]
-  // pub fn day_10_part_2_example_works() {
-  //   let contents = read_input(EXAMPLE_INPUT_FILENAME).unwrap();
-  //   let result = part_2(&contents);
-  //   assert_eq!(result, 2);
-  // }
-
-  // #[test]
-  // pub fn day_10_part_2_solution_works() {
-  //   let contents = read_input(INPUT_FILENAME).unwrap();
-  //   let result = part_2(&contents);
-  //   assert_eq!(result, 1118);
-  // }
+  pub fn day_10_part_2_example_6_works() {
+    let contents = read_day_input(10, EXAMPLE_INPUT_6).unwrap();
+    let result = part_2(&contents).unwrap();
+    assert_eq!(result, 10);
+  }
+
+  #[test]
+  pub fn day_10_starting_pipe_is_inferred() {
+    let starting_pipe = |input: &str| {
+      PipeMap::from_input(input)
+        .unwrap()
+        .starting_pipe()
+    };
+
+    assert_eq!(starting_pipe(".....\n.S-7.\n.|.|.\n.L-J.\n....."), 'F');
+    assert_eq!(
+      starting_pipe(&read_day_input(10, EXAMPLE_INPUT_6).unwrap()),
+      '7'
+    );
+
+    let pipe_map = PipeMap::from_input(".....\n.S-7.\n.|.|.\n.L-J.\n.....").unwrap();
+    assert_eq!(pipe_map.pipes().find(|char| *char == 'S'), None);
+  }
+
+  #[test]
+  pub fn day_10_loop_is_walked_in_order() {
+    let pipe_map = PipeMap::from_input(".....\n.S-7.\n.|.|.\n.L-J.\n.....").unwrap();
+    let pipe_loop = pipe_map
+      .pipe_loop()
+      .iter()
+      .map(|coordinates| (coordinates.x, coordinates.y))
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      pipe_loop,
+      vec![
+        (1, 1),
+        (2, 1),
+        (3, 1),
+        (3, 2),
+        (3, 3),
+        (2, 3),
+        (1, 3),
+        (1, 2)
+      ]
+    );
+    assert!(pipe_map.is_on_loop(Position::new(3, 2)));
+    assert!(!pipe_map.is_on_loop(Position::new(2, 2)));
+  }
+
+  #[test]
+  pub fn day_10_broken_loops_are_reported() {
+    let error = part_1(".....\n.S-7.\n.|.|.\n.L-..\n.....").unwrap_err();
+    assert_eq!((error.line, error.column), (4, 4));
+    assert_eq!(
+      error.message,
+      "expected a pipe carrying on the loop from 'S'"
+    );
+
+    let error = part_1(".....\n.S-7.\n.|.|.\n.L-7.\n.....").unwrap_err();
+    assert_eq!((error.line, error.column), (4, 4));
+  }
+
+  #[test]
+  pub fn day_10_invalid_starts_are_reported() {
+    let error = part_1(".....\n.S-7.\n...|.\n.L-J.\n.....").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 2));
+    assert_eq!(
+      error.message,
+      "expected two pipes to connect to 'S', found 1"
+    );
+
+    let error = part_1(".....\n.F-7.\n-S.|.\n.L-J.\n.....").unwrap_err();
+    assert_eq!((error.line, error.column), (3, 2));
+    assert_eq!(
+      error.message,
+      "expected 'S' to be a single pipe, but 3 pipes connect to it"
+    );
+  }
 }
@@ -0,0 +1,199 @@
+use super::PipeMap;
+use crate::grid::{Grid, Position};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const START: &str = "\x1b[1;33m";
+const INSIDE: &str = "\x1b[1;32m";
+const OUTSIDE: &str = "\x1b[2;31m";
+
+// 256-colour palette entries running from blue through green and yellow to red, nearest to
+// farthest from the start
+const HEATMAP: &[u8] = &[
+  21, 27, 33, 39, 45, 51, 50, 49, 48, 47, 46, 82, 118, 154, 190, 226, 220, 214, 208, 202, 196,
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Charset {
+  // Pipes as |, - and + for every corner
+  Ascii,
+  // Pipes as box-drawing characters
+  #[default]
+  Unicode,
+}
+
+// What to show on top of the pipes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Overlay {
+  #[default]
+  None,
+  // Marks every tile off the loop I(nside) or O(utside)
+  Enclosed,
+  // Shades the loop by distance from the start, or shows the last digit of it without colour
+  Distances,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RenderOptions {
+  pub charset: Charset,
+  pub overlay: Overlay,
+  // ANSI colours. The loop is bold and pipes that aren't part of it are dimmed. Without colour
+  // those pipes are drawn as ground instead, so that the loop still stands out
+  pub color: bool,
+}
+
+fn glyph(charset: Charset, char: char) -> char {
+  match (charset, char) {
+    (Charset::Unicode, '|') => '│',
+    (Charset::Unicode, '-') => '─',
+    (Charset::Unicode, 'L') => '└',
+    (Charset::Unicode, 'J') => '┘',
+    (Charset::Unicode, '7') => '┐',
+    (Charset::Unicode, 'F') => '┌',
+    (Charset::Unicode, _) => ' ',
+    (Charset::Ascii, 'L' | 'J' | '7' | 'F') => '+',
+    (Charset::Ascii, '|' | '-') => char,
+    (Charset::Ascii, _) => '.',
+  }
+}
+
+fn heatmap_style(distance: u32, max_distance: u32) -> String {
+  let index = (distance as usize * (HEATMAP.len() - 1)) / (max_distance.max(1) as usize);
+  format!("\x1b[1;38;5;{}m", HEATMAP[index])
+}
+
+fn paint(output: &mut String, char: char, style: Option<&str>) {
+  match style {
+    Some(style) => {
+      output.push_str(style);
+      output.push(char);
+      output.push_str(RESET);
+    }
+    None => output.push(char),
+  }
+}
+
+// The map row by row, each row ending in a newline
+pub fn render(pipe_map: &PipeMap, options: &RenderOptions) -> String {
+  let pipes = pipe_map.pipes();
+  let enclosed = match options.overlay {
+    Overlay::Enclosed => Some(pipe_map.enclosed_tiles()),
+    _ => None,
+  };
+
+  let mut distances = Grid::filled(pipes.width(), pipes.height(), None);
+  let mut max_distance = 0;
+  if options.overlay == Overlay::Distances {
+    for (distance, pipe_piece) in pipe_map.build_pipe_loop_with_distances() {
+      distances[pipe_piece.coordinates] = Some(distance);
+      max_distance = max_distance.max(distance);
+    }
+  }
+
+  let mut output = String::new();
+
+  for y in 0..pipes.height() {
+    for x in 0..pipes.width() {
+      let coordinates = Position::new(x, y);
+      let char = pipes[coordinates];
+
+      let (char, style) = if coordinates == pipe_map.starting_coordinates() {
+        ('S', Some(START.to_string()))
+      } else if pipe_map.is_on_loop(coordinates) {
+        match distances[coordinates] {
+          Some(distance) if options.color => (
+            glyph(options.charset, char),
+            Some(heatmap_style(distance, max_distance)),
+          ),
+          Some(distance) => (char::from_digit(distance % 10, 10).unwrap(), None),
+          None => (glyph(options.charset, char), Some(BOLD.to_string())),
+        }
+      } else {
+        match &enclosed {
+          Some(enclosed) if enclosed[coordinates] => ('I', Some(INSIDE.to_string())),
+          Some(_) => ('O', Some(OUTSIDE.to_string())),
+          None if options.color && char != '.' => {
+            (glyph(options.charset, char), Some(DIM.to_string()))
+          }
+          None => (glyph(options.charset, '.'), None),
+        }
+      };
+
+      paint(
+        &mut output,
+        char,
+        style.as_deref().filter(|_| options.color),
+      );
+    }
+
+    output.push('\n');
+  }
+
+  output
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{render, Charset, Overlay, RenderOptions};
+  use crate::day_10::PipeMap;
+  use crate::utils::read_day_input;
+
+  const SQUARE_WITH_JUNK: &str = "-L|F7\n7S-7|\nL|7||\n-L-J|\nL|-JF";
+
+  fn render_input(input: &str, options: RenderOptions) -> String {
+    render(&PipeMap::from_input(input).unwrap(), &options)
+  }
+
+  #[test]
+  pub fn day_10_loop_is_drawn_with_box_drawing_characters() {
+    let rendered = render_input(SQUARE_WITH_JUNK, RenderOptions::default());
+    assert_eq!(rendered, "     \n S─┐ \n │ │ \n └─┘ \n     \n");
+  }
+
+  #[test]
+  pub fn day_10_ascii_rendering_draws_corners_as_plus() {
+    let options = RenderOptions {
+      charset: Charset::Ascii,
+      ..Default::default()
+    };
+    let rendered = render_input(SQUARE_WITH_JUNK, options);
+    assert_eq!(rendered, ".....\n.S-+.\n.|.|.\n.+-+.\n.....\n");
+  }
+
+  #[test]
+  pub fn day_10_enclosed_overlay_marks_inside_and_outside() {
+    let options = RenderOptions {
+      charset: Charset::Ascii,
+      overlay: Overlay::Enclosed,
+      ..Default::default()
+    };
+    let contents = read_day_input(10, "example_input_4.txt").unwrap();
+    let rendered = render_input(&contents, options);
+
+    assert_eq!(rendered.matches('I').count(), 4);
+    assert_eq!(rendered.lines().nth(6), Some("O|II||II|O"));
+  }
+
+  #[test]
+  pub fn day_10_distances_overlay_shows_the_last_digit() {
+    let options = RenderOptions {
+      overlay: Overlay::Distances,
+      ..Default::default()
+    };
+    let rendered = render_input(SQUARE_WITH_JUNK, options);
+    assert_eq!(rendered, "     \n S12 \n 1 3 \n 234 \n     \n");
+  }
+
+  #[test]
+  pub fn day_10_colour_highlights_the_loop_and_dims_junk() {
+    let options = RenderOptions {
+      color: true,
+      ..Default::default()
+    };
+    let rendered = render_input(SQUARE_WITH_JUNK, options);
+
+    assert!(rendered.starts_with("\x1b[2m─\x1b[0m"));
+    assert!(rendered.contains("\x1b[1;33mS\x1b[0m\x1b[1m─\x1b[0m"));
+  }
+}
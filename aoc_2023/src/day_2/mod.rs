@@ -1,18 +1,30 @@
-const INPUT_FILENAME: &'static str = "./src/day_2/input.txt";
+use std::io::BufRead;
+
+use crate::parse::{input_lines, InputLine, LineReader, Lines, ParseError};
+use crate::solver::{Answer, Example, Part, PartResult, Solver};
+
+const DAY: u8 = 2;
+
+const EXAMPLES: &[Example] = &[Example {
+  name: "example_input.txt",
+  input: include_str!("example_input.txt"),
+  part_1: Some("8"),
+  part_2: Some("2286"),
+}];
 
 const MAX_RED_CUBES: u32 = 12;
 const MAX_GREEN_CUBES: u32 = 13;
 const MAX_BLUE_CUBES: u32 = 14;
 
 #[derive(Debug)]
-struct Handful {
+pub struct Handful {
   red_cubes: u32,
   green_cubes: u32,
   blue_cubes: u32,
 }
 
 impl Handful {
-  pub fn from_summary_text(handful_summary: &str) -> Handful {
+  pub fn from_summary_text(line: &InputLine, handful_summary: &str) -> Result<Handful, ParseError> {
     let statements = handful_summary.split(", ");
 
     let mut red_cubes: u32 = 0;
@@ -20,12 +32,9 @@ impl Handful {
     let mut blue_cubes: u32 = 0;
 
     for statement in statements {
-      let (count_as_string, cube_color) = match statement.split(" ").collect::<Vec<_>>()[..] {
-        [count_as_string, cube_color] => (count_as_string, cube_color),
-        _ => ("0", ""),
-      };
+      let (count_as_string, cube_color) = line.split_once(statement, " ")?;
 
-      let count = count_as_string.parse::<u32>().unwrap();
+      let count = line.parse::<u32>(count_as_string, "a cube count")?;
 
       match cube_color {
         "red" => {
@@ -37,15 +46,15 @@ impl Handful {
         "blue" => {
           blue_cubes = count;
         }
-        _ => (),
+        _ => return Err(line.error(cube_color, "expected red, green or blue")),
       }
     }
 
-    Handful {
+    Ok(Handful {
       red_cubes,
       green_cubes,
       blue_cubes,
-    }
+    })
   }
 
   pub fn is_valid(&self) -> bool {
@@ -60,30 +69,24 @@ impl Handful {
 }
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
   id: u32,
   handfuls: Vec<Handful>,
 }
 
 impl Game {
-  pub fn from_line(line: &str) -> Game {
-    let (title, game_summary) = match line.split(":").collect::<Vec<_>>()[..] {
-      [title, text] => (title, text),
-      _ => ("", ""),
-    };
+  pub fn from_line(line: &InputLine) -> Result<Game, ParseError> {
+    let (title, game_summary) = line.split_once(line.text, ":")?;
 
-    let id = match title.split(" ").collect::<Vec<_>>()[..] {
-      [_, id_as_string] => id_as_string.parse::<u32>().unwrap(),
-      _ => 0,
-    };
+    let id = line.parse::<u32>(line.strip_prefix(title, "Game ")?, "a game id")?;
 
     let handfuls = game_summary
       .trim()
       .split("; ")
-      .map(|handful_summary| Handful::from_summary_text(handful_summary))
-      .collect::<Vec<_>>();
+      .map(|handful_summary| Handful::from_summary_text(line, handful_summary))
+      .collect::<Result<Vec<_>, _>>()?;
 
-    Game { id, handfuls }
+    Ok(Game { id, handfuls })
   }
 
   pub fn is_valid(&self) -> bool {
@@ -119,77 +122,88 @@ impl Game {
   }
 }
 
-pub fn part_1(contents: &str) -> u32 {
-  let games = contents
-    .lines()
-    .map(|line| Game::from_line(line))
-    .filter(|game| game.is_valid())
-    .collect::<Vec<_>>();
+pub fn games_from_input(contents: &str) -> Result<Vec<Game>, ParseError> {
+  input_lines(DAY, contents)
+    .map(|line| Game::from_line(&line))
+    .collect()
+}
 
-  let total = games.iter().fold(0, |acc, game| acc + game.id);
+pub fn part_1(lines: impl Lines) -> Result<u32, ParseError> {
+  lines.try_fold_lines(DAY, 0, |acc, line| {
+    let game = Game::from_line(&line)?;
+    Ok(if game.is_valid() { acc + game.id } else { acc })
+  })
+}
 
-  total
+pub fn part_2(lines: impl Lines) -> Result<u32, ParseError> {
+  lines.try_fold_lines(DAY, 0, |acc, line| {
+    Ok(acc + Game::from_line(&line)?.min_cube_set().power())
+  })
 }
 
-pub fn part_2(contents: &str) -> u32 {
-  let games = contents
-    .lines()
-    .map(|line| Game::from_line(line))
-    .collect::<Vec<_>>();
+pub struct Day2;
 
-  let total = games
-    .iter()
-    .fold(0, |acc, game| acc + game.min_cube_set().power());
+impl Solver for Day2 {
+  fn day(&self) -> u8 {
+    DAY
+  }
 
-  total
-}
+  fn title(&self) -> &'static str {
+    "Cube Conundrum"
+  }
 
-pub fn run() -> Result<(), std::io::Error> {
-  println!("running day 2");
-  println!("reading contents of {}", INPUT_FILENAME);
-  let contents = crate::utils::read_input(INPUT_FILENAME)?;
+  fn examples(&self) -> &'static [Example] {
+    EXAMPLES
+  }
 
-  let part_1_total = part_1(&contents);
-  println!("part_1 total {}", part_1_total);
+  fn part_1(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_1(input)?)))
+  }
 
-  let part_2_total = part_2(&contents);
-  println!("part_2 total {}", part_2_total);
+  fn part_2(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_2(input)?)))
+  }
+
+  fn solve_lines(&self, part: Part, reader: &mut dyn BufRead) -> PartResult {
+    let lines = LineReader::new(reader);
+    let result = match part {
+      Part::One => part_1(lines)?,
+      Part::Two => part_2(lines)?,
+    };
+    Ok(Some(Answer::from(result)))
+  }
 
-  Ok(())
+  fn parse(&self, _part: Part, input: &str) -> Option<Result<(), ParseError>> {
+    Some(games_from_input(input).map(|_| ()))
+  }
 }
 
 #[cfg(test)]
 mod tests {
-  use super::{part_1, part_2, INPUT_FILENAME};
-  use crate::utils::read_input;
+  use super::{part_1, part_2};
+  use crate::utils::read_day_input;
 
-  const EXAMPLE_INPUT_FILENAME: &'static str = "./src/day_2/example_input.txt";
+  const EXAMPLE_INPUT_FILENAME: &str = "example_input.txt";
 
   #[test]
-  pub fn part_1_example_works() {
-    let contents = read_input(EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 8);
+  pub fn malformed_game_reports_its_position() {
+    let contents = "Game 1: 3 blue, 4 red\nGame 2: 1 blue, 2 purple";
+    let error = part_1(contents).unwrap_err();
+    assert_eq!((error.line, error.column), (2, 19));
+    assert_eq!(error.text, "purple");
   }
 
   #[test]
-  pub fn part_1_solution_works() {
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 2679);
+  pub fn part_1_example_works() {
+    let contents = read_day_input(2, EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_1(&contents).unwrap();
+    assert_eq!(result, 8);
   }
 
   #[test]
   pub fn part_2_example_works() {
-    let contents = read_input(EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
+    let contents = read_day_input(2, EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_2(&contents).unwrap();
     assert_eq!(result, 2286);
   }
-
-  #[test]
-  pub fn part_2_solution_works() {
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
-    assert_eq!(result, 77607);
-  }
 }
@@ -0,0 +1,109 @@
+use std::io::Error;
+
+use super::{is_valid_symbol, GridNumber, Schematic};
+use crate::image::{ppm, ImageFormat, Rgb, Svg};
+
+const GROUND: Rgb = Rgb(24, 24, 32);
+const PART_NUMBER: Rgb = Rgb(80, 200, 120);
+const OTHER_NUMBER: Rgb = Rgb(96, 96, 104);
+const SYMBOL: Rgb = Rgb(90, 150, 230);
+const GEAR: Rgb = Rgb(240, 90, 60);
+
+// Part numbers, numbers without a symbol next to them, symbols and gears each get their own colour.
+// Err if the image would be too large
+pub fn export(schematic: &Schematic, format: ImageFormat, scale: usize) -> Result<Vec<u8>, Error> {
+  let mut colors = schematic.grid.map(|value| {
+    if is_valid_symbol(*value) {
+      SYMBOL
+    } else {
+      GROUND
+    }
+  });
+
+  for gear in schematic.gears() {
+    colors[gear.position] = GEAR;
+  }
+
+  match format {
+    ImageFormat::Ppm => {
+      for number in schematic.numbers() {
+        let color = number_color(schematic, number);
+        for position in number.bounds.positions() {
+          colors[position] = color;
+        }
+      }
+
+      ppm(&colors, scale)
+    }
+    ImageFormat::Svg => {
+      let grid = &schematic.grid;
+      let mut svg = Svg::new(grid.width(), grid.height(), scale, GROUND);
+
+      // Each number is a single box rather than a box per digit
+      for number in schematic.numbers() {
+        let bounds = number.bounds;
+        svg.rect(
+          bounds.min.x as f64,
+          bounds.min.y as f64,
+          (bounds.max.x - bounds.min.x + 1) as f64,
+          (bounds.max.y - bounds.min.y + 1) as f64,
+          number_color(schematic, number),
+        );
+      }
+
+      for (position, color) in colors.iter() {
+        if *color != GROUND {
+          svg.rect(position.x as f64, position.y as f64, 1.0, 1.0, *color);
+        }
+      }
+
+      Ok(svg.finish().into_bytes())
+    }
+  }
+}
+
+fn number_color(schematic: &Schematic, number: &GridNumber) -> Rgb {
+  if schematic.has_symbol_neighbor(number) {
+    PART_NUMBER
+  } else {
+    OTHER_NUMBER
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{export, GEAR, GROUND, OTHER_NUMBER, PART_NUMBER, SYMBOL};
+  use crate::day_3::Schematic;
+  use crate::image::test_support::pixel;
+  use crate::image::ImageFormat;
+
+  const SCHEMATIC: &str = "467..114..\n...*......\n..35...#..";
+
+  #[test]
+  pub fn day_3_ppm_colours_numbers_symbols_and_gears() {
+    let schematic = Schematic::from_raw_contents(SCHEMATIC).unwrap();
+    let image = export(&schematic, ImageFormat::Ppm, 1).unwrap();
+
+    assert!(image.starts_with(b"P6\n10 3\n255\n"));
+    assert_eq!(pixel(&image, 10, 3, 0, 0), PART_NUMBER);
+    assert_eq!(pixel(&image, 10, 3, 5, 0), OTHER_NUMBER);
+    assert_eq!(pixel(&image, 10, 3, 3, 0), GROUND);
+    assert_eq!(pixel(&image, 10, 3, 3, 1), GEAR);
+    assert_eq!(pixel(&image, 10, 3, 7, 2), SYMBOL);
+  }
+
+  #[test]
+  pub fn day_3_svg_draws_a_box_per_number() {
+    let schematic = Schematic::from_raw_contents(SCHEMATIC).unwrap();
+    let image = String::from_utf8(export(&schematic, ImageFormat::Svg, 8).unwrap()).unwrap();
+
+    assert!(image.contains(&format!(
+      "<rect x=\"0\" y=\"0\" width=\"3\" height=\"1\" fill=\"{}\"/>",
+      PART_NUMBER.hex()
+    )));
+    assert!(image.contains(&format!(
+      "<rect x=\"3\" y=\"1\" width=\"1\" height=\"1\" fill=\"{}\"/>",
+      GEAR.hex()
+    )));
+  }
+}
@@ -1,360 +1,410 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+
 use itertools::Itertools;
-use std::{collections::HashMap, io::Error};
 
-use crate::utils::read_input;
+use crate::grid::{BoundingBox, Grid, Position};
+use crate::parse::{input_lines, InputLine, ParseError};
+use crate::solver::{Answer, Example, Part, PartResult, SolveError, Solver};
 
-pub const INPUT_FILENAME: &'static str = "./src/day_3/input.txt";
-pub const EXAMPLE_INPUT_FILENAME: &'static str = "./src/day_3/example_input.txt";
+pub mod export;
 
-// Cloneable struct representing an abstract 2D position
-#[derive(Debug, Clone)]
-struct Position {
-  x: i32,
-  y: i32,
-}
+const DAY: u8 = 3;
 
-impl Position {
-  fn zero() -> Position {
-    Position { x: 0, y: 0 }
-  }
+const EXAMPLES: &[Example] = &[Example {
+  name: "example_input.txt",
+  input: include_str!("example_input.txt"),
+  part_1: Some("4361"),
+  part_2: Some("467835"),
+}];
 
-  fn get_neighbor_positions(&self) -> [Position; 8] {
-    let Position { x, y } = *self;
-
-    [
-      // top row
-      Position { x: x - 1, y: y - 1 },
-      Position { x, y: y - 1 },
-      Position { x: x + 1, y: y - 1 },
-      // center row
-      Position { x: x - 1, y },
-      Position { x: x + 1, y },
-      // bottom row
-      Position { x: x - 1, y: y + 1 },
-      Position { x, y: y + 1 },
-      Position { x: x + 1, y: y + 1 },
-    ]
-  }
-
-  fn as_key(&self) -> String {
-    format!("{}:{}", self.x, self.y)
-  }
+fn is_valid_symbol(value: char) -> bool {
+  value != '.' && !value.is_ascii_digit()
 }
 
-impl PartialEq for Position {
-  fn eq(&self, other: &Self) -> bool {
-    self.x == other.x && self.y == other.y
-  }
+fn is_gear(value: char) -> bool {
+  value == '*'
 }
 
-// A single cell of a grid. Only 1 cell per position exists
-#[derive(Debug, Clone)]
-struct Cell {
-  position: Position,
-  value: char,
+// A run of digits within a row and the number they spell out
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridNumber {
+  pub total: u32,
+  pub bounds: BoundingBox,
 }
 
-impl Cell {
-  fn is_valid_symbol(&self) -> bool {
-    self.value != '.' && !self.value.is_digit(10)
-  }
-
-  fn is_gear(&self) -> bool {
-    self.value == '*'
-  }
-
-  fn is_digit(&self) -> bool {
-    self.value.is_digit(10)
-  }
-}
+impl GridNumber {
+  fn new(row: &[char], y: usize, columns: Range<usize>) -> GridNumber {
+    let total = row[columns.clone()]
+      .iter()
+      .collect::<String>()
+      // Schematic::from_raw_contents has already checked that every number fits in a u32
+      .parse::<u32>()
+      .unwrap();
 
-impl PartialEq for Cell {
-  fn eq(&self, other: &Self) -> bool {
-    self.position == other.position && self.value == other.value
+    GridNumber {
+      total,
+      bounds: BoundingBox {
+        min: Position::new(columns.start, y),
+        max: Position::new(columns.end - 1, y),
+      },
+    }
   }
 }
 
-// A group of 1+ cells with number values
-#[derive(Debug)]
-struct GridNumber<'a> {
-  cells: Vec<&'a Cell>,
-  total: u32,
+// A symbol in the schematic and the distinct numbers touching it, including diagonally
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolNeighbors<'a> {
+  pub symbol: char,
+  pub position: Position,
+  pub numbers: Vec<&'a GridNumber>,
 }
 
-impl<'a> GridNumber<'a> {
-  fn new(cells: Vec<&Cell>) -> GridNumber<'_> {
-    let total = cells
+impl<'a> SymbolNeighbors<'a> {
+  pub fn sum(&self) -> u64 {
+    self
+      .numbers
       .iter()
-      // For the sake of brevity/speed I'll assume these cells have digit values
-      .fold(String::new(), |acc, cell| {
-        acc + cell.value.to_string().as_str()
-      })
-      .parse::<u32>()
-      .unwrap();
+      .map(|number| number.total as u64)
+      .sum()
+  }
 
-    GridNumber { cells, total }
+  pub fn product(&self) -> u64 {
+    self
+      .numbers
+      .iter()
+      .map(|number| number.total as u64)
+      .product()
   }
 }
 
-impl<'a> PartialEq for GridNumber<'a> {
-  fn eq(&self, other: &Self) -> bool {
-    self.cells == other.cells && self.total == other.total
-  }
+// Every number has to fit in a u32
+fn validate_schematic_line(line: &InputLine) -> Result<(), ParseError> {
+  line
+    .text
+    .split(|char: char| !char.is_ascii_digit())
+    .filter(|number| !number.is_empty())
+    .try_for_each(|number| {
+      line
+        .parse::<u32>(number, "a part number that fits in 32 bits")
+        .map(|_| ())
+    })
 }
 
-// Grid of characters
-struct Grid {
-  cells: Vec<Cell>,
-  max_position: Position,
-  cell_position_map: HashMap<String, Cell>,
+// Engine schematic of part numbers and symbols. Each digit cell knows which number it belongs to,
+// so finding the numbers around a symbol is a handful of lookups rather than a search
+pub struct Schematic {
+  grid: Grid<char>,
+  numbers: Vec<GridNumber>,
+  number_at: Grid<Option<usize>>,
 }
 
-impl Grid {
-  pub fn from_raw_contents(contents: &str) -> Grid {
-    let cells = contents
-      .lines()
-      .enumerate()
-      .flat_map(move |(y, line)| {
-        line
-          .chars()
-          .enumerate()
-          .map(move |(x, value)| Cell {
-            position: Position {
-              x: x as i32,
-              y: y as i32,
-            },
-            value,
-          })
-      })
-      .collect::<Vec<_>>();
+// Numbers never run on from one row into the next
+fn get_grid_numbers(grid: &Grid<char>) -> Vec<GridNumber> {
+  let mut result = Vec::new();
 
-    let max_position = cells
-      .iter()
-      .fold(Position::zero(), |mut acc, cell| {
-        if cell.position.x > acc.x {
-          acc.x = cell.position.x;
-        }
+  for (y, row) in grid.rows().enumerate() {
+    let mut number_start = None;
 
-        if cell.position.y > acc.y {
-          acc.y = cell.position.y;
+    for (x, value) in row.iter().enumerate() {
+      match (value.is_ascii_digit(), number_start) {
+        (true, None) => number_start = Some(x),
+        (false, Some(start)) => {
+          result.push(GridNumber::new(row, y, start..x));
+          number_start = None;
         }
+        _ => {}
+      }
+    }
 
-        acc
-      });
+    if let Some(start) = number_start {
+      result.push(GridNumber::new(row, y, start..row.len()));
+    }
+  }
 
-    let cell_position_map = cells
-      .iter()
-      .fold(HashMap::new(), |mut acc, cell| {
-        let key = cell.position.as_key();
-        // I don't really like cloning this here, but it's much easier
-        // Than wrapping everything in Rc
-        acc.insert(key, cell.clone());
-        acc
-      });
+  result
+}
+
+impl Schematic {
+  pub fn from_raw_contents(contents: &str) -> Result<Schematic, ParseError> {
+    let grid = Grid::parse(DAY, contents, "a schematic character", Some)?;
+    input_lines(DAY, contents).try_for_each(|line| validate_schematic_line(&line))?;
+
+    let numbers = get_grid_numbers(&grid);
 
-    Grid {
-      cells,
-      max_position,
-      cell_position_map,
+    let mut number_at = grid.map(|_| None);
+    for (number_index, number) in numbers.iter().enumerate() {
+      for position in number.bounds.positions() {
+        number_at[position] = Some(number_index);
+      }
     }
-  }
 
-  fn is_valid_cell_position(&self, position: &Position) -> bool {
-    position.x >= 0
-      && position.y >= 0
-      && position.x <= self.max_position.x
-      && position.y <= self.max_position.y
+    Ok(Schematic {
+      grid,
+      numbers,
+      number_at,
+    })
   }
 
-  fn get_valid_neighbor_positions(&self, cell: &Cell) -> Vec<Position> {
-    cell
-      .position
-      .get_neighbor_positions()
-      .into_iter()
-      .filter(|position| self.is_valid_cell_position(position))
-      .collect::<Vec<_>>()
+  // Every number in the schematic, row by row
+  pub fn numbers(&self) -> &[GridNumber] {
+    &self.numbers
   }
 
-  // It is assumed that target_cell is a cell in this Grid
-  fn get_cell_neighbors(&self, target_cell: &Cell) -> impl Iterator<Item = &Cell> {
+  fn has_symbol_neighbor(&self, number: &GridNumber) -> bool {
     self
-      .get_valid_neighbor_positions(&target_cell)
-      .into_iter()
-      .map(|position| {
-        let cell_from_map = self
-          .cell_position_map
-          .get(&position.as_key())
-          .unwrap();
-
-        // My original solution found cells like this, which added ~2s to the overall
-        // computation time every time this function was called, for the input.txt data set (140x140)
-        /*
-        let cell_from_list = self
-          .cells
-          .iter()
-          .find(|cell| cell.position == position)
-          // get_valid_neighbor_positions ensures there will be a neighbor at that position
-          // so unwrapping here is ok
-          .unwrap();
-        */
-
-        cell_from_map
-      })
+      .grid
+      .surrounding(number.bounds)
+      .any(|neighbor| is_valid_symbol(self.grid[neighbor]))
   }
 
-  fn get_grid_numbers(&self) -> Vec<GridNumber> {
-    let mut result = Vec::new();
-
-    let mut cell_buffer: Vec<&Cell> = Vec::new();
-
-    for cell in self.cells.iter() {
-      if cell.value.is_digit(10) {
-        cell_buffer.push(&cell);
-      } else {
-        if cell_buffer.len() > 0 {
-          result.push(GridNumber::new(cell_buffer))
-        }
-
-        cell_buffer = Vec::new();
-      }
-    }
-
-    result
+  // Indexes of the distinct numbers touching a cell, in the order they're first seen
+  fn get_neighbor_numbers(&self, position: Position) -> Vec<usize> {
+    self
+      .grid
+      .neighbours_8(position)
+      .filter_map(|neighbor| self.number_at[neighbor])
+      .unique()
+      .collect()
   }
 
-  fn get_grid_number_symbol_neighbors<'a>(
-    &'a self,
-    gridnumber: &'a GridNumber,
-  ) -> impl Iterator<Item = &'a Cell> {
-    gridnumber
-      .cells
+  // Every symbol matching `is_symbol` with the numbers around it, row by row. Digits and '.' are
+  // never symbols, whatever the predicate says
+  pub fn symbol_neighbors(
+    &self,
+    is_symbol: impl Fn(char) -> bool,
+  ) -> impl Iterator<Item = SymbolNeighbors<'_>> {
+    self
+      .grid
       .iter()
-      .map(|cell| {
-        self
-          .get_cell_neighbors(cell)
+      .filter(move |(_, value)| is_valid_symbol(**value) && is_symbol(**value))
+      .map(|(position, value)| SymbolNeighbors {
+        symbol: *value,
+        position,
+        numbers: self
+          .get_neighbor_numbers(position)
           .into_iter()
-          .filter(|cell_neighbor| !gridnumber.cells.contains(&cell_neighbor))
+          .map(|index| &self.numbers[index])
+          .collect(),
       })
-      .flatten()
-      .dedup()
-      .filter(|neighbor| neighbor.is_valid_symbol())
   }
 
-  fn get_valid_grid_numbers(&self) -> impl Iterator<Item = GridNumber> {
+  // symbol_neighbors grouped by symbol
+  pub fn symbol_report(
+    &self,
+    is_symbol: impl Fn(char) -> bool,
+  ) -> BTreeMap<char, Vec<SymbolNeighbors<'_>>> {
     self
-      .get_grid_numbers()
-      .into_iter()
-      .filter(|gridnumber| {
-        let neighbors = self
-          .get_grid_number_symbol_neighbors(gridnumber)
-          .collect::<Vec<_>>();
-        neighbors.len() > 0
+      .symbol_neighbors(is_symbol)
+      .fold(BTreeMap::new(), |mut acc, neighbors| {
+        acc
+          .entry(neighbors.symbol)
+          .or_insert_with(Vec::new)
+          .push(neighbors);
+        acc
       })
   }
 
-  fn get_valid_grid_number_sum(&self) -> u32 {
+  // Overflowing a u64 would take more than 2^32 numbers, each of them under 2^32
+  pub fn get_valid_grid_number_sum(&self) -> u64 {
     self
-      .get_valid_grid_numbers()
-      .fold(0, |acc, grid_number| acc + grid_number.total)
+      .numbers
+      .iter()
+      .filter(|number| self.has_symbol_neighbor(number))
+      .map(|number| number.total as u64)
+      .sum()
   }
 
-  fn get_valid_grid_number_gear_ratio_sum(&self) -> u32 {
-    let gridnumbers = self.get_grid_numbers();
-
+  // Gears without exactly 2 gridnumber neighbors are invalid
+  pub fn gears(&self) -> impl Iterator<Item = SymbolNeighbors<'_>> {
     self
-      .cells
-      .iter()
-      // Get all gear symbol cells
-      .filter(|cell| cell.is_gear())
-      // Find adjacent gridnumbers to cell
-      .map(|gear_cell| {
-        self
-          // Find digit cell neighbors first
-          .get_cell_neighbors(gear_cell)
-          .filter(|cell| cell.is_digit())
-          // then map digit cells to gridnumber
-          .map(|cell| {
-            gridnumbers
-              .iter()
-              .find(|gridnumber| gridnumber.cells.contains(&cell))
-              .unwrap()
-          })
-          .dedup()
-          .collect::<Vec<_>>()
-      })
-      // Gears without exactly 2 gridnumber neighbors are invalid
-      .filter(|neighbors| neighbors.len() == 2)
-      // Add total
-      .fold(0, |acc, neighbors| {
-        let neighbors_sum = neighbors
-          .iter()
-          .map(|neighbor| neighbor.total)
-          .reduce(|acc, total| acc * total)
-          .unwrap();
-
-        acc + neighbors_sum
-      })
+      .symbol_neighbors(is_gear)
+      .filter(|neighbors| neighbors.numbers.len() == 2)
+  }
+
+  // Each ratio is the product of two u32s and so fits in a u64, but their sum might not
+  pub fn get_valid_grid_number_gear_ratio_sum(&self) -> Result<u64, SolveError> {
+    self.gears().try_fold(0u64, |acc, neighbors| {
+      acc
+        .checked_add(neighbors.product())
+        .ok_or_else(|| {
+          SolveError::overflow(
+            DAY,
+            neighbors.position.y + 1,
+            "the gear ratio sum doesn't fit in 64 bits",
+          )
+        })
+    })
   }
 }
 
-// too low: 560570
-pub fn part_1(contents: &str) -> u32 {
-  let grid = Grid::from_raw_contents(contents);
+pub fn part_1(contents: &str) -> Result<u64, ParseError> {
+  let schematic = Schematic::from_raw_contents(contents)?;
 
-  grid.get_valid_grid_number_sum()
+  Ok(schematic.get_valid_grid_number_sum())
 }
 
-pub fn part_2(contents: &str) -> u32 {
-  let grid = Grid::from_raw_contents(contents);
+pub fn part_2(contents: &str) -> Result<u64, SolveError> {
+  let schematic = Schematic::from_raw_contents(contents)?;
 
-  grid.get_valid_grid_number_gear_ratio_sum()
+  schematic.get_valid_grid_number_gear_ratio_sum()
 }
 
-pub fn run() -> Result<(), Error> {
-  println!("running day 3");
-  println!("reading contents of {}", INPUT_FILENAME);
-  let contents = read_input(INPUT_FILENAME)?;
+pub struct Day3;
+
+impl Solver for Day3 {
+  fn day(&self) -> u8 {
+    DAY
+  }
+
+  fn title(&self) -> &'static str {
+    "Gear Ratios"
+  }
 
-  let part_1_total = part_1(&contents);
-  println!("part_1 total {}", part_1_total);
+  fn examples(&self) -> &'static [Example] {
+    EXAMPLES
+  }
 
-  let part_2_total = part_2(&contents);
-  println!("part_2 total {}", part_2_total);
+  fn part_1(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_1(input)?)))
+  }
 
-  Ok(())
+  fn part_2(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_2(input)?)))
+  }
+
+  fn parse(&self, _part: Part, input: &str) -> Option<Result<(), ParseError>> {
+    Some(Schematic::from_raw_contents(input).map(|_| ()))
+  }
 }
 
 #[cfg(test)]
 mod tests {
-  use super::{part_1, part_2, EXAMPLE_INPUT_FILENAME, INPUT_FILENAME};
-  use crate::utils::read_input;
+  use super::{part_1, part_2, Schematic};
+  use crate::grid::{BoundingBox, Position};
+  use crate::solver::SolveError;
+  use crate::utils::read_day_input;
+
+  const EXAMPLE_INPUT_FILENAME: &str = "example_input.txt";
 
   #[test]
-  pub fn day_3_part_1_example_works() {
-    let contents = read_input(EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 4361);
+  pub fn day_3_ragged_rows_are_rejected() {
+    let error = part_1("467..\n...*..\n..35.").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 1));
+  }
+
+  #[test]
+  pub fn day_3_numbers_stop_at_the_end_of_each_row() {
+    let schematic = Schematic::from_raw_contents("..12\n34*.\n...5").unwrap();
+    let numbers = schematic
+      .numbers()
+      .iter()
+      .map(|number| (number.total, number.bounds))
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      numbers,
+      vec![
+        (
+          12,
+          BoundingBox {
+            min: Position::new(2, 0),
+            max: Position::new(3, 0)
+          }
+        ),
+        (
+          34,
+          BoundingBox {
+            min: Position::new(0, 1),
+            max: Position::new(1, 1)
+          }
+        ),
+        (
+          5,
+          BoundingBox {
+            min: Position::new(3, 2),
+            max: Position::new(3, 2)
+          }
+        ),
+      ]
+    );
   }
 
   #[test]
-  pub fn day_3_part_1_solution_works() {
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    // assert!(result > 560570);
-    assert_eq!(result, 560670);
+  pub fn day_3_edge_and_last_cell_numbers_are_counted() {
+    let contents = "..12\n34*.\n...5";
+    assert_eq!(part_1(contents).unwrap(), 12 + 34 + 5);
+    assert_eq!(part_2(contents).unwrap(), 0);
+    assert_eq!(part_2("...2\n..*.\n.3..").unwrap(), 6);
   }
 
   #[test]
-  pub fn day_3_part_2_example_works() {
-    let contents = read_input(EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
-    assert_eq!(result, 467835);
+  pub fn day_3_symbols_can_be_queried_by_predicate() {
+    let contents = read_day_input(3, EXAMPLE_INPUT_FILENAME).unwrap();
+    let schematic = Schematic::from_raw_contents(&contents).unwrap();
+
+    let sums = schematic
+      .symbol_report(|_| true)
+      .into_iter()
+      .map(|(symbol, neighbors)| (symbol, neighbors.iter().map(|n| n.sum()).sum::<u64>()))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      sums,
+      vec![
+        ('#', 633),
+        ('$', 664),
+        ('*', 467 + 35 + 617 + 755 + 598),
+        ('+', 592)
+      ]
+    );
+
+    let crowded = schematic
+      .symbol_neighbors(|symbol| symbol == '*')
+      .filter(|neighbors| neighbors.numbers.len() >= 2)
+      .map(|neighbors| neighbors.position)
+      .collect::<Vec<_>>();
+    assert_eq!(crowded, vec![Position::new(3, 1), Position::new(5, 8)]);
+  }
+
+  // Every other row is "12*34." repeated, so each gear sits between exactly two numbers
+  #[test]
+  pub fn day_3_large_schematics_are_solved() {
+    let rows = format!("{}\n{}", "12*34.".repeat(100), "......".repeat(100));
+    let contents = vec![rows.as_str(); 600].join("\n");
+
+    assert_eq!(part_1(&contents).unwrap(), 600 * 100 * (12 + 34));
+    assert_eq!(part_2(&contents).unwrap(), 600 * 100 * 12 * 34);
   }
 
   #[test]
-  pub fn day_3_part_2_solution_works() {
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
-    assert_eq!(result, 91622824);
+  pub fn day_3_totals_past_32_bits_are_counted() {
+    assert_eq!(part_1("4000000000*4000000000").unwrap(), 8_000_000_000);
+    assert_eq!(
+      part_2("4000000000*4000000000").unwrap(),
+      16_000_000_000_000_000_000
+    );
+  }
+
+  #[test]
+  pub fn day_3_overflowing_gear_ratio_sums_are_reported() {
+    let contents = "4000000000*4000000000\n.....................\n4000000000*4000000000";
+    assert_eq!(
+      part_2(contents).unwrap_err(),
+      SolveError::overflow(3, 3, "the gear ratio sum doesn't fit in 64 bits")
+    );
+  }
+
+  #[test]
+  pub fn day_3_part_1_example_works() {
+    let contents = read_day_input(3, EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_1(&contents).unwrap();
+    assert_eq!(result, 4361);
+  }
+
+  #[test]
+  pub fn day_3_part_2_example_works() {
+    let contents = read_day_input(3, EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_2(&contents).unwrap();
+    assert_eq!(result, 467835);
   }
 }
@@ -1,139 +1,197 @@
-use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::io::BufRead;
 
-use crate::utils::read_input;
+use crate::parse::{input_lines, InputLine, LineReader, Lines, ParseError};
+use crate::solver::{Answer, Example, Part, PartResult, SolveError, Solver};
 
-const INPUT_FILENAME: &str = "./src/day_4/input.txt";
+const DAY: u8 = 4;
+
+const EXAMPLES: &[Example] = &[Example {
+  name: "example_input.txt",
+  input: include_str!("example_input.txt"),
+  part_1: Some("13"),
+  part_2: Some("30"),
+}];
 
 #[derive(Clone)]
-struct Card<'a> {
-  winning_numbers: Vec<&'a str>,
-  numbers: Vec<&'a str>,
+struct Card {
+  winning_numbers: Vec<u32>,
+  numbers: Vec<u32>,
 }
 
-impl<'a> Card<'a> {
-  fn from_input(line: &str) -> Card {
-    let (_, number_groups) = line.split_once(":").unwrap();
-    let (raw_winning_numbers, raw_numbers) = number_groups.split_once("|").unwrap();
+impl Card {
+  fn from_input(line: &InputLine) -> Result<Card, ParseError> {
+    let (_, number_groups) = line.split_once(line.text, ":")?;
+    let (raw_winning_numbers, raw_numbers) = line.split_once(number_groups, "|")?;
 
-    let winning_numbers = get_number_list_from_string(raw_winning_numbers);
-    let numbers = get_number_list_from_string(raw_numbers);
+    let winning_numbers = get_number_list_from_string(line, raw_winning_numbers)?;
+    let numbers = get_number_list_from_string(line, raw_numbers)?;
 
-    Card {
+    Ok(Card {
       winning_numbers,
       numbers,
-    }
+    })
   }
 }
 
-fn get_number_list_from_string(input: &str) -> Vec<&str> {
+fn get_number_list_from_string(line: &InputLine, input: &str) -> Result<Vec<u32>, ParseError> {
   input
     .trim()
     .split(" ")
-    .filter(|num| num.len() > 0)
-    .collect::<Vec<_>>()
+    .filter(|num| !num.is_empty())
+    .map(|num| line.parse::<u32>(num, "a card number"))
+    .collect()
 }
 
-fn create_cards_from_input(input: &str) -> Vec<Card<'_>> {
-  input
-    .lines()
-    .map(|line| Card::from_input(line))
-    .collect::<Vec<_>>()
+impl Card {
+  fn winning_number_count(&self) -> usize {
+    self
+      .numbers
+      .iter()
+      .filter(|number| self.winning_numbers.contains(number))
+      .count()
+  }
 }
 
-fn part_1(contents: &str) -> u32 {
-  create_cards_from_input(contents)
-    .iter()
-    .map(|card| {
-      card.numbers.iter().fold(0, |acc, number| {
-        if card.winning_numbers.contains(number) {
-          match acc {
-            0 => 1,
-            _ => acc * 2,
-          }
-        } else {
-          acc
-        }
-      })
-    })
-    .fold(0, |acc, card_points| acc + card_points)
+fn create_cards_from_input(input: &str) -> Result<Vec<Card>, ParseError> {
+  input_lines(DAY, input)
+    .map(|line| Card::from_input(&line))
+    .collect()
 }
 
-fn part_2(contents: &str) -> u32 {
-  let cards = create_cards_from_input(contents);
-
-  let card_instances = cards
-    .iter()
-    .enumerate()
-    // accumulator = key: card idx & value: number of instances of that card
-    .fold(vec![1; cards.len()], |mut acc, (index, card)| {
-      let winning_number_count = card.numbers.iter().fold(0, |acc, number| {
-        if card.winning_numbers.contains(number) {
-          acc + 1
-        } else {
-          acc
-        }
-      });
+fn part_1(lines: impl Lines) -> Result<u64, SolveError> {
+  lines.try_fold_lines(DAY, 0, |acc: u64, line| {
+    let points = match Card::from_input(&line)?.winning_number_count() {
+      0 => Some(0),
+      count => u32::try_from(count - 1)
+        .ok()
+        .and_then(|shift| 1u64.checked_shl(shift)),
+    };
+
+    points
+      .and_then(|points| acc.checked_add(points))
+      .ok_or_else(|| SolveError::overflow(DAY, line.number, "the points don't fit in 64 bits"))
+  })
+}
 
-      let current_card_count = acc.get(index).unwrap().clone();
-      for card_number in index + 1..index + 1 + winning_number_count {
-        acc[card_number] += current_card_count;
+fn part_2(lines: impl Lines) -> Result<u64, SolveError> {
+  // Extra copies won by earlier cards, starting with the current card. Only as many cards ahead
+  // as a single card can win are ever tracked
+  let mut pending_copies: VecDeque<u64> = VecDeque::new();
+
+  lines.try_fold_lines(DAY, 0, |acc: u64, line| {
+    let card = Card::from_input(&line)?;
+    let overflow =
+      || SolveError::overflow(DAY, line.number, "the card count doesn't fit in 64 bits");
+    let card_count = pending_copies
+      .pop_front()
+      .unwrap_or(0)
+      .checked_add(1)
+      .ok_or_else(overflow)?;
+
+    for index in 0..card.winning_number_count() {
+      match pending_copies.get_mut(index) {
+        Some(copies) => {
+          *copies = copies
+            .checked_add(card_count)
+            .ok_or_else(overflow)?
+        }
+        None => pending_copies.push_back(card_count),
       }
+    }
 
-      acc
-    });
-
-  card_instances
-    .iter()
-    .fold(0, |acc, number| acc + number)
+    acc.checked_add(card_count).ok_or_else(overflow)
+  })
 }
 
-pub fn run() -> Result<(), std::io::Error> {
-  println!("running day 4");
-  println!("reading contents of {}", INPUT_FILENAME);
-  let contents = read_input(INPUT_FILENAME)?;
+pub struct Day4;
+
+impl Solver for Day4 {
+  fn day(&self) -> u8 {
+    DAY
+  }
+
+  fn title(&self) -> &'static str {
+    "Scratchcards"
+  }
+
+  fn examples(&self) -> &'static [Example] {
+    EXAMPLES
+  }
 
-  let part_1_total = part_1(&contents);
-  println!("part_1 total {}", part_1_total);
+  fn part_1(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_1(input)?)))
+  }
 
-  let part_2_total = part_2(&contents);
-  println!("part_2 total {}", part_2_total);
+  fn part_2(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_2(input)?)))
+  }
+
+  fn solve_lines(&self, part: Part, reader: &mut dyn BufRead) -> PartResult {
+    let lines = LineReader::new(reader);
+    let result = match part {
+      Part::One => part_1(lines)?,
+      Part::Two => part_2(lines)?,
+    };
+    Ok(Some(Answer::from(result)))
+  }
 
-  Ok(())
+  fn parse(&self, _part: Part, input: &str) -> Option<Result<(), ParseError>> {
+    Some(create_cards_from_input(input).map(|_| ()))
+  }
 }
 
 #[cfg(test)]
 mod tests {
-  use crate::utils::read_input;
+  use crate::solver::SolveError;
+  use crate::utils::read_day_input;
+
+  use super::{part_1, part_2};
 
-  use super::{part_1, part_2, INPUT_FILENAME};
+  const EXAMPLE_INPUT_FILENAME: &str = "example_input.txt";
 
-  const EXAMPLE_INPUT_FILENAME: &str = "./src/day_4/example_input.txt";
+  // `card_count` cards that each have `matches` winning numbers
+  fn cards(card_count: usize, matches: usize) -> String {
+    let numbers = (1..=matches)
+      .map(|number| number.to_string())
+      .collect::<Vec<_>>()
+      .join(" ");
+
+    (1..=card_count)
+      .map(|card| format!("Card {}: {} | {}", card, numbers, numbers))
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
 
   #[test]
-  pub fn day_4_part_1_example_works() {
-    let contents = read_input(EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 13);
+  pub fn day_4_totals_past_32_bits_are_counted() {
+    assert_eq!(part_1(&cards(1, 33)).unwrap(), 1 << 32);
+    assert_eq!(part_2(&cards(60, 2)).unwrap(), 10610209857660);
   }
 
   #[test]
-  pub fn day_4_part_1_solution_works() {
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 18519);
+  pub fn day_4_overflowing_totals_are_reported() {
+    assert_eq!(
+      part_1(&cards(1, 65)).unwrap_err(),
+      SolveError::overflow(4, 1, "the points don't fit in 64 bits")
+    );
+    assert!(matches!(
+      part_2(&cards(100, 2)).unwrap_err(),
+      SolveError::Overflow { day: 4, .. }
+    ));
   }
 
   #[test]
-  pub fn day_4_part_2_example_works() {
-    let contents = read_input(EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
-    assert_eq!(result, 30);
+  pub fn day_4_part_1_example_works() {
+    let contents = read_day_input(4, EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_1(&contents).unwrap();
+    assert_eq!(result, 13);
   }
 
   #[test]
-  pub fn day_4_part_2_solution_works() {
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
-    assert_eq!(result, 11787590);
+  pub fn day_4_part_2_example_works() {
+    let contents = read_day_input(4, EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_2(&contents).unwrap();
+    assert_eq!(result, 30);
   }
 }
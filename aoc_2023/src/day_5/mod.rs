@@ -1,105 +1,134 @@
-use std::ops::{Range, RangeInclusive};
+use std::ops::RangeInclusive;
 
 use itertools::Itertools;
 
-const INPUT_FILENAME: &str = "./src/day_5/input.txt";
+use crate::parse::{end_of_input_error, input_sections, InputLine, ParseError};
+use crate::solver::{Answer, Example, Part, PartResult, Solver};
 
-fn get_min_in_vec(vec: &Vec<i64>) -> i64 {
+const DAY: u8 = 5;
+
+const EXAMPLES: &[Example] = &[Example {
+  name: "example_input.txt",
+  input: include_str!("example_input.txt"),
+  part_1: Some("35"),
+  part_2: Some("46"),
+}];
+
+fn get_min_in_vec(vec: &[i64]) -> i64 {
   vec
     .iter()
     .fold(i64::MAX, |acc, num| if *num < acc { *num } else { acc })
 }
 
-fn ranges_overlap(a: &Range<i64>, b: &Range<i64>) -> bool {
-  false
-}
-
-// Taken from https://stackoverflow.com/questions/26998485/is-it-possible-to-print-a-number-formatted-with-thousand-separator-in-rust
-fn format_number_with_commas(num: i64) -> String {
-  num
-    .to_string()
-    .as_bytes()
-    .rchunks(3)
-    .rev()
-    .map(std::str::from_utf8)
-    .collect::<Result<Vec<&str>, _>>()
-    .unwrap()
-    .join(",")
-}
-
-/*
-// Algorithm from
-// https://stackoverflow.com/questions/3269434/whats-the-most-efficient-way-to-test-if-two-ranges-overlap
-fn ranges_overlap(a: &Range<i64>, b: &Range<i64>) -> bool {
-  (a.start >= b.start && a.start <= b.end)
-    || (a.end >= b.start && a.end <= b.end)
-    || (b.start >= a.start && b.start <= a.end)
-    || (b.end >= a.start && b.end <= a.end)
-}
-*/
-
 struct AlmanacMap {
   source_range: RangeInclusive<i64>,
   dest_range: RangeInclusive<i64>,
 }
 
 impl AlmanacMap {
-  fn from_input(input: &str) -> AlmanacMap {
-    let (dest_start, source_start, range_length) = match input.split(" ").collect::<Vec<_>>()[..] {
+  fn from_input(line: &InputLine) -> Result<AlmanacMap, ParseError> {
+    let (dest_start, source_start, range_length) = match line.text.split(' ').collect::<Vec<_>>()[..]
+    {
       [raw_dest_start, raw_source_start, raw_range_length] => (
-        raw_dest_start.parse::<i64>().unwrap(),
-        raw_source_start.parse::<i64>().unwrap(),
-        raw_range_length.parse::<i64>().unwrap(),
+        line.parse::<i64>(raw_dest_start, "a destination range start")?,
+        line.parse::<i64>(raw_source_start, "a source range start")?,
+        line.parse::<i64>(raw_range_length, "a range length")?,
       ),
-      // This isn't going to happen
-      _ => (0, 0, 0),
+      _ => return Err(line.error(line.text, "expected 3 numbers")),
     };
-    println!(
+    log::trace!(
       "creating AlmanacMap: dest {} src {} range {}",
-      dest_start, source_start, range_length
+      dest_start,
+      source_start,
+      range_length
     );
 
-    let source_range = source_start..=source_start + range_length;
-    let dest_range = dest_start..=dest_start + range_length;
+    let source_range = source_start..=source_start + range_length - 1;
+    let dest_range = dest_start..=dest_start + range_length - 1;
 
-    AlmanacMap {
+    Ok(AlmanacMap {
       source_range,
       dest_range,
-    }
+    })
   }
 }
 
-fn create_almanac_map_from_input(input: String) -> Vec<AlmanacMap> {
-  // println!("input: {}", input);
-  let (_, mapping_def_lines) = input.split_once('\n').unwrap();
+fn create_almanac_map_from_input(lines: &[InputLine]) -> Result<Vec<AlmanacMap>, ParseError> {
+  // The first line is the "x-to-y map:" heading
+  let (heading, mapping_def_lines) = lines.split_first().unwrap();
+  if !heading.text.ends_with("map:") {
+    return Err(heading.error(heading.text, "expected a map heading"));
+  }
 
-  println!("creating {} AlmanacMaps", mapping_def_lines.len());
+  log::debug!("creating {} AlmanacMaps", mapping_def_lines.len());
 
   mapping_def_lines
-    .split("\n")
+    .iter()
     .map(AlmanacMap::from_input)
-    .collect_vec()
+    .collect()
+}
+
+struct Almanac<'a> {
+  seed_line: InputLine<'a>,
+  seeds: Vec<i64>,
+  almanac_map_categories: Vec<Vec<AlmanacMap>>,
+}
+
+impl<'a> Almanac<'a> {
+  fn from_input(input: &'a str) -> Result<Almanac<'a>, ParseError> {
+    let sections = input_sections(DAY, input);
+
+    let (seed_line, almanac_map_sections) = match sections.split_first() {
+      Some((seed_section, almanac_map_sections)) => (seed_section[0], almanac_map_sections),
+      None => return Err(end_of_input_error(DAY, input, "expected a seeds line")),
+    };
+
+    let seeds = seed_line
+      .strip_prefix(seed_line.text, "seeds: ")?
+      .split(' ')
+      .map(|int_as_str| seed_line.parse::<i64>(int_as_str, "a seed number"))
+      .collect::<Result<Vec<_>, _>>()?;
+
+    let almanac_map_categories = almanac_map_sections
+      .iter()
+      .map(|section| create_almanac_map_from_input(section))
+      .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Almanac {
+      seed_line,
+      seeds,
+      almanac_map_categories,
+    })
+  }
+
+  // Part 2 reads the seeds line as pairs of range start & range width
+  fn seed_ranges(&self) -> Result<Vec<RangeInclusive<i64>>, ParseError> {
+    if !self.seeds.len().is_multiple_of(2) {
+      return Err(self.seed_line.error(
+        self.seed_line.text,
+        "expected pairs of seed range starts and widths",
+      ));
+    }
+
+    Ok(
+      self
+        .seeds
+        .chunks(2)
+        .map(|chunk| chunk[0]..=chunk[0] + chunk[1] - 1)
+        .collect_vec(),
+    )
+  }
 }
 
-fn part_1(input: &str) -> i64 {
-  let chunks = input.split("\n\n").collect::<Vec<_>>();
-
-  let (seed_defs, source_dest_map_defs) = match chunks.as_slice() {
-    [seed_defs, source_dest_map_defs @ ..] => (seed_defs, source_dest_map_defs),
-    _ => return 0,
-  };
-
-  let seeds = seed_defs
-    .split_once("seeds: ")
-    .unwrap()
-    .1
-    .split(' ')
-    .map(|int_as_str| int_as_str.parse::<i64>().unwrap())
-    .collect::<Vec<_>>();
-
-  let result = source_dest_map_defs
-    .into_iter()
-    .map(|input| create_almanac_map_from_input(String::from(*input)))
+fn part_1(input: &str) -> Result<i64, ParseError> {
+  let Almanac {
+    seeds,
+    almanac_map_categories,
+    ..
+  } = Almanac::from_input(input)?;
+
+  let result = almanac_map_categories
+    .iter()
     .fold(seeds, |acc, almanac_maps| {
       acc
         .iter()
@@ -122,177 +151,136 @@ fn part_1(input: &str) -> i64 {
     });
 
   // return min value in array
-  get_min_in_vec(&result)
+  Ok(get_min_in_vec(&result))
 }
 
-fn part_2(input: &str) -> i64 {
-  let chunks = input.split("\n\n").collect::<Vec<_>>();
+// Splits a seed range against every map in a single category. The parts of the range covered
+// by a map are shifted to that map's destination, anything left over maps to itself
+fn map_seed_range_through_category(
+  seed_range: &RangeInclusive<i64>,
+  almanac_maps: &[AlmanacMap],
+) -> Vec<RangeInclusive<i64>> {
+  let mut mapped_ranges = Vec::new();
 
-  let (seed_ranges, almanac_map_categories) = match chunks.as_slice() {
-    [seed_line, almanac_map_lines @ ..] => {
-      let (_, raw_seed_numbers) = seed_line.split_once("seeds: ").unwrap();
+  let unmapped_ranges = almanac_maps
+    .iter()
+    .fold(vec![seed_range.clone()], |acc, almanac_map| {
+      let AlmanacMap {
+        source_range,
+        dest_range,
+      } = almanac_map;
+      let source_dest_diff = dest_range.start() - source_range.start();
 
-      let seed_ranges = raw_seed_numbers
-        .split(' ')
-        .map(|int_as_str| int_as_str.parse::<i64>().unwrap())
-        .chunks(2)
+      acc
         .into_iter()
-        .map(|chunk| match chunk.collect::<Vec<_>>()[..] {
-          [range_start, range_width] => range_start..=range_start + range_width,
-          // this won't happen
-          _ => 0..=0,
+        .flat_map(|range| {
+          let overlap_start = std::cmp::max(*range.start(), *source_range.start());
+          let overlap_end = std::cmp::min(*range.end(), *source_range.end());
+
+          // no overlap - leave the range for the next map in the category
+          if overlap_start > overlap_end {
+            return vec![range];
+          }
+
+          mapped_ranges.push(overlap_start + source_dest_diff..=overlap_end + source_dest_diff);
+
+          // Whatever is left on either side of the overlap is still unmapped
+          let mut remainder = Vec::new();
+          if *range.start() < overlap_start {
+            remainder.push(*range.start()..=overlap_start - 1);
+          }
+          if *range.end() > overlap_end {
+            remainder.push(overlap_end + 1..=*range.end());
+          }
+
+          remainder
         })
-        .collect_vec();
+        .collect_vec()
+    });
 
-      let almanac_map_categories = almanac_map_lines
-        .into_iter()
-        .map(|input| create_almanac_map_from_input(String::from(*input)))
-        .collect_vec();
+  mapped_ranges.extend(unmapped_ranges);
+  mapped_ranges
+}
 
-      (seed_ranges, almanac_map_categories)
-    }
-    _ => return 0,
-  };
+fn part_2(input: &str) -> Result<i64, ParseError> {
+  let almanac = Almanac::from_input(input)?;
 
   let seed_ranges =
-    almanac_map_categories
-      .into_iter()
-      .flatten()
-      .fold(seed_ranges, |acc, almanac_map| {
+    almanac
+      .almanac_map_categories
+      .iter()
+      .fold(almanac.seed_ranges()?, |acc, almanac_maps| {
         acc
           .iter()
-          .flat_map(|seed_range| {
-            let AlmanacMap {
-              source_range,
-              dest_range,
-            } = &almanac_map;
-
-            println!("seed_range: {:?}", seed_range);
-            println!("source_range: {:?}", source_range);
-            let source_dest_diff = dest_range.start() - source_range.start();
-            let dest_seed_range =
-              seed_range.start() + source_dest_diff..=seed_range.end() + source_dest_diff;
-
-            println!("dest_range: {:?}", dest_range);
-            println!("diff: {:?}", source_dest_diff);
-            // TODO - explain
-            let result = if seed_range.start() >= source_range.start()
-              && seed_range.end() <= source_range.end()
-            {
-              println!("inside: seed: {:?} source: {:?}", seed_range, source_range);
-              // source completely covers seed_range
-              vec![dest_seed_range]
-            } else if seed_range.start() < source_range.start()
-              && seed_range.end() > source_range.start()
-              && seed_range.end() <= source_range.end()
-            {
-              println!(
-                "starts before: seed: {:?} source: {:?}",
-                seed_range, source_range
-              );
-              // seed range partially overlaps - starts before source
-              // split into 2 ranges
-              vec![
-                *seed_range.start()..=*source_range.start() - 1,
-                *dest_range.start()..=*dest_seed_range.end(),
-              ]
-            } else if seed_range.start() >= source_range.start()
-              && seed_range.start() < source_range.end()
-              && seed_range.end() > source_range.end()
-            {
-              println!(
-                "starts after: seed: {:?} source: {:?}",
-                seed_range, source_range
-              );
-              // seed range partially overlaps - starts after source
-              // split into 2 ranges
-              vec![
-                *dest_seed_range.start()..=*dest_range.end(),
-                *source_range.end() + 1..=*seed_range.end(),
-              ]
-            } else if seed_range.start() < source_range.start()
-              && seed_range.end() > source_range.end()
-            {
-              println!(
-                "starts before and after: seed: {:?} source: {:?}",
-                seed_range, source_range
-              );
-              // seed range overlaps - starts before source and ends after source
-              // split into 3 ranges
-              vec![
-                *seed_range.start()..=*source_range.start() - 1,
-                dest_range.clone(),
-                *source_range.end() + 1..=*seed_range.end(),
-              ]
-            } else {
-              println!(
-                "no overlap: seed: {:?} source: {:?}",
-                seed_range, source_range
-              );
-              vec![seed_range.clone()]
-            };
-            println!("result: {:?}", result);
-            println!("---");
-            result
-          })
+          .flat_map(|seed_range| map_seed_range_through_category(seed_range, almanac_maps))
           .collect_vec()
       });
 
-  seed_ranges
-    .into_iter()
-    .fold(i64::MAX, |acc, seed_range| {
-      std::cmp::min(acc, *seed_range.start())
-    })
+  Ok(
+    seed_ranges
+      .into_iter()
+      .fold(i64::MAX, |acc, seed_range| {
+        std::cmp::min(acc, *seed_range.start())
+      }),
+  )
 }
 
-pub fn run() -> Result<(), std::io::Error> {
-  println!("running day 5");
-  let contents = crate::utils::read_input(INPUT_FILENAME)?;
+pub struct Day5;
 
-  // let part_1_total = part_1(&contents);
-  // println!("part_1 total {}", part_1_total);
+impl Solver for Day5 {
+  fn day(&self) -> u8 {
+    DAY
+  }
+
+  fn title(&self) -> &'static str {
+    "If You Give A Seed A Fertilizer"
+  }
+
+  fn examples(&self) -> &'static [Example] {
+    EXAMPLES
+  }
+
+  fn part_1(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_1(input)?)))
+  }
 
-  let part_2_total = part_2(&contents);
-  println!("part_2 total {}", part_2_total);
+  fn part_2(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_2(input)?)))
+  }
 
-  Ok(())
+  fn parse(&self, part: Part, input: &str) -> Option<Result<(), ParseError>> {
+    Some(Almanac::from_input(input).and_then(|almanac| match part {
+      Part::One => Ok(()),
+      Part::Two => almanac.seed_ranges().map(|_| ()),
+    }))
+  }
 }
 
 #[cfg(test)]
 mod tests {
-  use crate::utils::read_input;
+  use crate::utils::read_day_input;
 
-  use super::{part_1, part_2, INPUT_FILENAME};
+  use super::{part_1, part_2};
 
-  const EXAMPLE_INPUT_FILENAME: &str = "./src/day_5/example_input.txt";
+  const EXAMPLE_INPUT_FILENAME: &str = "example_input.txt";
 
   #[test]
-  pub fn day_5_part_1_example_works() {
-    let contents = read_input(EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 35);
+  pub fn day_5_odd_seed_count_is_rejected_by_part_2() {
+    let error = part_2("seeds: 79 14 55\n\nseed-to-soil map:\n50 98 2").unwrap_err();
+    assert_eq!(error.line, 1);
   }
 
   #[test]
-  pub fn day_5_part_1_solution_works() {
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 379811651);
+  pub fn day_5_part_1_example_works() {
+    let contents = read_day_input(5, EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_1(&contents).unwrap();
+    assert_eq!(result, 35);
   }
 
   #[test]
   pub fn day_5_part_2_example_works() {
-    let contents = read_input(EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
+    let contents = read_day_input(5, EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_2(&contents).unwrap();
     assert_eq!(result, 46);
   }
-
-  /*
-  #[test]
-  pub fn day_4_part_2_solution_works() {
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
-    assert_eq!(result, 11787590);
-  }
-  */
 }
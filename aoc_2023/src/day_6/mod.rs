@@ -1,43 +1,71 @@
-pub const INPUT_FILENAME: &str = "src/day_6/input.txt";
+use crate::parse::{end_of_input_error, input_lines, InputLine, ParseError};
+use crate::solver::{Answer, Example, Part, PartResult, Solver};
+
+const DAY: u8 = 6;
+
+const EXAMPLES: &[Example] = &[Example {
+  name: "example_input.txt",
+  input: include_str!("example_input.txt"),
+  part_1: Some("288"),
+  part_2: Some("71503"),
+}];
 
 type RaceTimeAndRecordDistance = (u64, u64);
 
-fn strip_heading_from_line(line: &str) -> &str {
-  let (_, rest) = line.split_once(':').unwrap();
-  rest
+fn strip_heading_from_line<'a>(line: &InputLine<'a>) -> Result<&'a str, ParseError> {
+  let (_, rest) = line.split_once(line.text, ":")?;
+  Ok(rest)
 }
 
-fn split_line_by_arbitrary_amounts_of_whitespace(line: &str) -> Vec<String> {
-  let mut result: Vec<String> = Vec::new();
-  let mut buffer = String::new();
-
-  let stripped_line = strip_heading_from_line(line);
+fn split_line_by_arbitrary_amounts_of_whitespace<'a>(
+  line: &InputLine<'a>,
+) -> Result<Vec<&'a str>, ParseError> {
+  let stripped_line = strip_heading_from_line(line)?;
+
+  Ok(
+    stripped_line
+      .split(' ')
+      .filter(|number_string| !number_string.is_empty())
+      .collect(),
+  )
+}
 
-  for char in String::from(stripped_line).chars() {
-    if char == ' ' {
-      if buffer.len() > 0 {
-        result.push(buffer);
-      }
+fn get_time_and_distance_lines(input: &str) -> Result<(InputLine<'_>, InputLine<'_>), ParseError> {
+  let mut lines = input_lines(DAY, input);
 
-      buffer = String::new();
-    } else {
-      buffer.push(char);
-    }
+  match (lines.next(), lines.next()) {
+    (Some(time_line), Some(distance_line)) => Ok((time_line, distance_line)),
+    _ => Err(end_of_input_error(
+      DAY,
+      input,
+      "expected a time line and a distance line",
+    )),
   }
+}
 
-  if buffer.len() > 0 {
-    result.push(buffer);
-  }
+fn parse_numbers_from_line(line: &InputLine) -> Result<Vec<u64>, ParseError> {
+  split_line_by_arbitrary_amounts_of_whitespace(line)?
+    .into_iter()
+    .map(|number_string| line.parse::<u64>(number_string, "a number"))
+    .collect()
+}
+
+// Part 2 ignores the spaces between numbers and reads the whole line as one number
+fn parse_joined_number_from_line(line: &InputLine) -> Result<u64, ParseError> {
+  let stripped_line = strip_heading_from_line(line)?;
+  let number_string = split_line_by_arbitrary_amounts_of_whitespace(line)?.join("");
 
-  result
+  number_string
+    .parse::<u64>()
+    .map_err(|_| line.error(stripped_line, "expected a number"))
 }
 
 fn get_valid_combination_count((race_time, record_distance): RaceTimeAndRecordDistance) -> u64 {
   let distances_travelled = (1..race_time).map(|button_press_time| {
     let speed = button_press_time;
     let time_left = race_time - button_press_time;
-    let distance_travelled = time_left * speed;
-    distance_travelled
+
+    time_left * speed
   });
 
   distances_travelled
@@ -46,26 +74,39 @@ fn get_valid_combination_count((race_time, record_distance): RaceTimeAndRecordDi
     .count() as u64
 }
 
-fn part_1(input: &str) -> u64 {
-  let (race_times, record_distances) = {
-    let mapped_lines = input
-      .split('\n')
+// Each race's time and record distance, read column by column
+fn races_from_input(input: &str) -> Result<Vec<(u64, u64)>, ParseError> {
+  let (time_line, distance_line) = get_time_and_distance_lines(input)?;
+  let race_times = parse_numbers_from_line(&time_line)?;
+  let record_distances = parse_numbers_from_line(&distance_line)?;
+
+  if race_times.len() != record_distances.len() {
+    return Err(distance_line.error(
+      distance_line.text,
+      format!("expected {} distances", race_times.len()),
+    ));
+  }
+
+  Ok(
+    race_times
       .into_iter()
-      .map(|line| {
-        let number_strings = split_line_by_arbitrary_amounts_of_whitespace(line);
-        number_strings
-          .into_iter()
-          .map(|number_string| number_string.parse::<u64>().unwrap())
-      })
-      .collect::<Vec<_>>();
-
-    (mapped_lines[0].clone(), mapped_lines[1].clone())
-  };
-
-  let race_times_and_record_distances = race_times
-    .into_iter()
-    .zip(record_distances)
-    .collect::<Vec<_>>();
+      .zip(record_distances)
+      .collect::<Vec<_>>(),
+  )
+}
+
+// Part 2 reads each line as a single number with the spaces removed
+fn race_from_input(input: &str) -> Result<(u64, u64), ParseError> {
+  let (time_line, distance_line) = get_time_and_distance_lines(input)?;
+
+  let race_time = parse_joined_number_from_line(&time_line)?;
+  let distance_travelled = parse_joined_number_from_line(&distance_line)?;
+
+  Ok((race_time, distance_travelled))
+}
+
+fn part_1(input: &str) -> Result<u64, ParseError> {
+  let race_times_and_record_distances = races_from_input(input)?;
 
   let record_breaker_counts = race_times_and_record_distances
     .into_iter()
@@ -74,78 +115,80 @@ fn part_1(input: &str) -> u64 {
     })
     .collect::<Vec<_>>();
 
-  let margin_of_error = record_breaker_counts
-    .iter()
-    .fold(1, |acc, num| acc * num);
+  let margin_of_error = record_breaker_counts.iter().product::<u64>();
 
-  margin_of_error as u64
+  Ok(margin_of_error)
 }
 
-fn part_2(input: &str) -> u64 {
-  let lines = input.split('\n').collect::<Vec<_>>();
+fn part_2(input: &str) -> Result<u64, ParseError> {
+  Ok(get_valid_combination_count(race_from_input(input)?))
+}
 
-  let race_time = {
-    let number_string_vec = split_line_by_arbitrary_amounts_of_whitespace(lines[0]);
-    let number_string = number_string_vec.join("");
-    number_string.parse::<u64>().unwrap()
-  };
-  let distance_travelled = {
-    let number_string_vec = split_line_by_arbitrary_amounts_of_whitespace(lines[1]);
-    let number_string = number_string_vec.join("");
-    number_string.parse::<u64>().unwrap()
-  };
+pub struct Day6;
 
-  let margin_of_error = get_valid_combination_count((race_time, distance_travelled));
+impl Solver for Day6 {
+  fn day(&self) -> u8 {
+    DAY
+  }
 
-  margin_of_error as u64
-}
+  fn title(&self) -> &'static str {
+    "Wait For It"
+  }
 
-pub fn run() -> Result<(), std::io::Error> {
-  println!("running day 6");
-  let contents = crate::utils::read_input(INPUT_FILENAME)?;
+  fn examples(&self) -> &'static [Example] {
+    EXAMPLES
+  }
 
-  let part_1_total = part_1(&contents);
-  println!("part_1 total {}", part_1_total);
+  fn part_1(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_1(input)?)))
+  }
 
-  let part_2_total = part_2(&contents);
-  println!("part_2 total {}", part_2_total);
+  fn part_2(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_2(input)?)))
+  }
 
-  Ok(())
+  fn parse(&self, part: Part, input: &str) -> Option<Result<(), ParseError>> {
+    match part {
+      Part::One => Some(races_from_input(input).map(|_| ())),
+      Part::Two => Some(race_from_input(input).map(|_| ())),
+    }
+  }
 }
 
 #[cfg(test)]
 mod tests {
-  use crate::utils::read_input;
+  use crate::utils::read_day_input;
 
-  use super::{part_1, part_2, INPUT_FILENAME};
+  use super::{part_1, part_2};
 
-  const EXAMPLE_INPUT_FILENAME: &str = "./src/day_6/example_input.txt";
+  const EXAMPLE_INPUT_FILENAME: &str = "example_input.txt";
 
   #[test]
-  pub fn day_6_part_1_example_works() {
-    let contents = read_input(EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 288);
+  pub fn day_6_missing_distance_line_is_reported() {
+    let error = part_1("Time:      7  15   30").unwrap_err();
+    assert_eq!(error.line, 2);
   }
 
   #[test]
-  pub fn day_6_part_1_solution_works() {
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 1159152);
+  pub fn day_6_mismatched_race_count_is_reported() {
+    let error = part_1("Time:      7  15   30\nDistance:  9  40").unwrap_err();
+    assert_eq!(
+      (error.line, error.message.as_str()),
+      (2, "expected 3 distances")
+    );
   }
 
   #[test]
-  pub fn day_6_part_2_example_works() {
-    let contents = read_input(EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
-    assert_eq!(result, 71503);
+  pub fn day_6_part_1_example_works() {
+    let contents = read_day_input(6, EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_1(&contents).unwrap();
+    assert_eq!(result, 288);
   }
 
   #[test]
-  pub fn day_6_part_2_solution_works() {
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
-    assert_eq!(result, 41513103);
+  pub fn day_6_part_2_example_works() {
+    let contents = read_day_input(6, EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_2(&contents).unwrap();
+    assert_eq!(result, 71503);
   }
 }
@@ -3,7 +3,17 @@ use std::collections::HashMap;
 use itertools::Itertools;
 use lazy_static::lazy_static;
 
-pub const INPUT_FILENAME: &str = "src/day_7/input.txt";
+use crate::parse::{InputLine, Lines, ParseError};
+use crate::solver::{Answer, Example, Part, PartResult, Solver};
+
+const DAY: u8 = 7;
+
+const EXAMPLES: &[Example] = &[Example {
+  name: "example_input.txt",
+  input: include_str!("example_input.txt"),
+  part_1: Some("6440"),
+  part_2: Some("5905"),
+}];
 
 const HAND_LENGTH: usize = 5;
 
@@ -58,14 +68,13 @@ struct Hand {
   bid: u32,
   hand_type: HandType,
   cards: Vec<char>,
-  substituted_cards: Vec<char>,
 }
 
-fn get_hand_type_from_cards(cards: &Vec<char>) -> HandType {
+fn get_hand_type_from_cards(cards: &[char]) -> HandType {
   let card_counts: HashMap<char, u32> = cards
     .iter()
     .fold(HashMap::new(), |mut acc, card_char| {
-      let next_count = match acc.get(&card_char) {
+      let next_count = match acc.get(card_char) {
         Some(count) => count + 1,
         None => 1,
       };
@@ -98,7 +107,7 @@ fn get_hand_type_from_cards(cards: &Vec<char>) -> HandType {
       card_counts_indexed_by_count
         .get(key)
         .unwrap()
-        .into_iter()
+        .iter()
         .map(|_| *key)
         .collect_vec()
     })
@@ -117,24 +126,41 @@ fn get_hand_type_from_cards(cards: &Vec<char>) -> HandType {
   }
 }
 
-fn hand_from_input_line_part_1(line: &str) -> Hand {
-  let (raw_cards_string, raw_bid) = line.split_once(" ").unwrap();
+// Both parts read hands the same way, they only differ in how jokers are treated
+fn cards_and_bid_from_input_line(line: &InputLine) -> Result<(Vec<char>, u32), ParseError> {
+  let (raw_cards_string, raw_bid) = line.split_once(line.text, " ")?;
 
-  let bid = raw_bid.parse::<u32>().unwrap();
+  let bid = line.parse::<u32>(raw_bid, "a bid")?;
 
   let cards = raw_cards_string.chars().collect_vec();
 
+  if cards.len() != HAND_LENGTH
+    || cards
+      .iter()
+      .any(|card| !CARD_POINT_MAP_PART_1.contains_key(card))
+  {
+    return Err(line.error(
+      raw_cards_string,
+      format!("expected a hand of {} cards", HAND_LENGTH),
+    ));
+  }
+
+  Ok((cards, bid))
+}
+
+fn hand_from_input_line_part_1(line: InputLine) -> Result<Hand, ParseError> {
+  let (cards, bid) = cards_and_bid_from_input_line(&line)?;
+
   let hand_type = get_hand_type_from_cards(&cards);
 
-  Hand {
+  Ok(Hand {
     bid,
     cards,
-    substituted_cards: vec![],
     hand_type,
-  }
+  })
 }
 
-fn get_card_count_map(cards: &Vec<char>) -> HashMap<&char, u32> {
+fn get_card_count_map(cards: &[char]) -> HashMap<&char, u32> {
   cards
     .iter()
     .fold(HashMap::new(), |mut acc, card_char| {
@@ -143,18 +169,14 @@ fn get_card_count_map(cards: &Vec<char>) -> HashMap<&char, u32> {
         None => 1,
       };
 
-      acc.insert(&card_char, next_count);
+      acc.insert(card_char, next_count);
 
       acc
     })
 }
 
-fn hand_from_input_line_part_2(line: &str) -> Hand {
-  let (raw_cards_string, raw_bid) = line.split_once(" ").unwrap();
-
-  let bid = raw_bid.parse::<u32>().unwrap();
-
-  let cards = raw_cards_string.chars().collect_vec();
+fn hand_from_input_line_part_2(line: InputLine) -> Result<Hand, ParseError> {
+  let (cards, bid) = cards_and_bid_from_input_line(&line)?;
 
   // without the joker's special logic factored in
   let base_card_counts = get_card_count_map(&cards);
@@ -182,7 +204,7 @@ fn hand_from_input_line_part_2(line: &str) -> Hand {
       });
 
   let card_to_substitute_joker_with = {
-    if base_card_counts_indexed_by_count.len() == 0 {
+    if base_card_counts_indexed_by_count.is_empty() {
       'A'
     } else {
       let highest_key: u32 = base_card_counts_indexed_by_count
@@ -229,32 +251,30 @@ fn hand_from_input_line_part_2(line: &str) -> Hand {
 
   let hand_type = get_hand_type_from_cards(&substituted_cards);
 
-  // println!("cards: {:?}", cards);
-  println!(
-    "card_to_substitute_joker_with: {:?}",
-    card_to_substitute_joker_with
+  log::trace!(
+    "cards: {:?}, card_to_substitute_joker_with: {:?}, hand_type: {:?}",
+    cards,
+    card_to_substitute_joker_with,
+    hand_type
   );
-  // println!("substituted_cards: {:?}", substituted_cards);
-  // println!("hand_type: {:?}\n", hand_type);
 
-  Hand {
+  Ok(Hand {
     bid,
     cards,
-    substituted_cards,
     hand_type,
-  }
+  })
 }
 
-fn group_hands_by_type<'a>(hands: &'a Vec<Hand>) -> HashMap<HandType, Vec<&'a Hand>> {
+fn group_hands_by_type(hands: &[Hand]) -> HashMap<HandType, Vec<&Hand>> {
   hands
     .iter()
     .fold(HashMap::new(), |mut acc, hand| {
       match acc.get_mut(&hand.hand_type) {
         Some(current) => {
-          current.push(&hand);
+          current.push(hand);
         }
         None => {
-          acc.insert(hand.hand_type.clone(), vec![&hand]);
+          acc.insert(hand.hand_type, vec![&hand]);
         }
       }
 
@@ -263,10 +283,10 @@ fn group_hands_by_type<'a>(hands: &'a Vec<Hand>) -> HashMap<HandType, Vec<&'a Ha
 }
 
 fn sort_hands_by_point_value<'a>(
-  hands: &'a Vec<&'a Hand>,
+  hands: &'a [&'a Hand],
   card_point_map: &CardPointMap,
 ) -> Vec<&'a Hand> {
-  let mut sorted = hands.clone();
+  let mut sorted = hands.to_vec();
   sorted.sort_by(|a, b| {
     use std::cmp::Ordering;
 
@@ -291,23 +311,30 @@ fn sort_hands_by_point_value<'a>(
   sorted
 }
 
-fn get_total_winnings(hands_sorted_by_rank: &Vec<&Hand>) -> u64 {
+fn get_total_winnings(hands_sorted_by_rank: &[&Hand]) -> u64 {
   hands_sorted_by_rank
-    .into_iter()
+    .iter()
     .enumerate()
     .map(|(index, hand)| {
       // rank = index + 1
       hand.bid as u64 * (index as u64 + 1)
     })
     // Add them all together
-    .fold(0, |acc, winnings| acc + winnings)
+    .sum()
 }
 
-fn part_1(input: &str) -> u64 {
-  let hands = input
-    .split('\n')
-    .map(hand_from_input_line_part_1)
-    .collect_vec();
+fn hands_from_input(
+  lines: impl Lines,
+  hand_from_input_line: fn(InputLine) -> Result<Hand, ParseError>,
+) -> Result<Vec<Hand>, ParseError> {
+  lines.try_fold_lines(DAY, Vec::new(), |mut hands, line| {
+    hands.push(hand_from_input_line(line)?);
+    Ok(hands)
+  })
+}
+
+fn part_1(lines: impl Lines) -> Result<u64, ParseError> {
+  let hands = hands_from_input(lines, hand_from_input_line_part_1)?;
 
   let hands_grouped_by_type = group_hands_by_type(&hands);
 
@@ -315,92 +342,89 @@ fn part_1(input: &str) -> u64 {
     .keys()
     // Sort keys low to high
     .sorted()
-    .into_iter()
     .map(|key| hands_grouped_by_type.get(key).unwrap())
     // Sort groups of hands using the second ordering rule from the prompt
     .flat_map(|hands| sort_hands_by_point_value(hands, &CARD_POINT_MAP_PART_1))
     .collect_vec();
 
-  get_total_winnings(&hands_sorted_by_rank)
+  Ok(get_total_winnings(&hands_sorted_by_rank))
 }
 
-fn part_2(input: &str) -> u64 {
-  let hands = input
-    .split('\n')
-    .map(hand_from_input_line_part_2)
-    .collect_vec();
+fn part_2(lines: impl Lines) -> Result<u64, ParseError> {
+  let hands = hands_from_input(lines, hand_from_input_line_part_2)?;
 
   let hands_grouped_by_type = group_hands_by_type(&hands);
 
-  // println!("hands sorted by rank:");
-
   let hands_sorted_by_rank = hands_grouped_by_type
     .keys()
     // Sort keys low to high
     .sorted()
-    .into_iter()
     .map(|key| hands_grouped_by_type.get(key).unwrap())
     // Sort groups of hands using the second ordering rule from the prompt
     // TODO - use the base card set, not the substituted card set
     .flat_map(|hands| sort_hands_by_point_value(hands, &CARD_POINT_MAP_PART_2))
     .collect_vec();
 
-  for (index, hand) in hands_sorted_by_rank.iter().enumerate() {
-    // println!("{}: {:?}", index + 1, hand);
+  Ok(get_total_winnings(&hands_sorted_by_rank))
+}
+
+pub struct Day7;
+
+impl Solver for Day7 {
+  fn day(&self) -> u8 {
+    DAY
   }
 
-  get_total_winnings(&hands_sorted_by_rank)
-}
+  fn title(&self) -> &'static str {
+    "Camel Cards"
+  }
 
-pub fn run() -> Result<(), std::io::Error> {
-  println!("running day 7");
-  let contents = crate::utils::read_input(INPUT_FILENAME)?;
+  fn examples(&self) -> &'static [Example] {
+    EXAMPLES
+  }
 
-  let part_1_total = part_1(&contents);
-  println!("part_1 total {}", part_1_total);
+  fn part_1(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_1(input)?)))
+  }
 
-  let part_2_total = part_2(&contents);
-  println!("part_2 total {}", part_2_total);
+  fn part_2(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_2(input)?)))
+  }
 
-  Ok(())
+  fn parse(&self, part: Part, input: &str) -> Option<Result<(), ParseError>> {
+    match part {
+      Part::One => Some(hands_from_input(input, hand_from_input_line_part_1).map(|_| ())),
+      Part::Two => Some(hands_from_input(input, hand_from_input_line_part_2).map(|_| ())),
+    }
+  }
 }
 
 #[cfg(test)]
 mod tests {
-  use crate::utils::read_input;
+  use crate::utils::read_day_input;
 
-  use super::{part_1, part_2, INPUT_FILENAME};
+  use super::{part_1, part_2};
 
-  const EXAMPLE_INPUT_FILENAME: &str = "./src/day_7/example_input.txt";
+  const EXAMPLE_INPUT_FILENAME: &str = "example_input.txt";
 
   #[test]
-  pub fn day_7_part_1_example_works() {
-    let contents = read_input(EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 6440);
+  pub fn day_7_bad_hand_is_reported() {
+    let error = part_1("32T3K 765\nT55X5 684").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 1));
+    assert_eq!(error.text, "T55X5");
   }
 
   #[test]
-  pub fn day_7_part_1_solution_works() {
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 247815719);
+  pub fn day_7_part_1_example_works() {
+    let contents = read_day_input(7, EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_1(&contents).unwrap();
+    assert_eq!(result, 6440);
   }
 
   #[test]
   pub fn day_7_part_2_example_works() {
-    let contents = read_input(EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
+    let contents = read_day_input(7, EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_2(&contents).unwrap();
     assert_eq!(result, 5905);
   }
-
-  #[test]
-  pub fn day_7_part_2_solution_works() {
-    use more_asserts::assert_gt;
-
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
-    assert_gt!(result, 248673364);
-    assert_eq!(result, 248747492);
-  }
 }
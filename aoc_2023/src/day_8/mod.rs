@@ -1,45 +1,112 @@
 use std::collections::HashMap;
 
-use itertools::Itertools;
+use crate::parse::{end_of_input_error, input_sections, InputLine, ParseError};
+use crate::solver::{Answer, Example, Part, PartResult, Solver};
+
+const DAY: u8 = 8;
+
+const EXAMPLES: &[Example] = &[
+  Example {
+    name: "part_1_example_input.txt",
+    input: include_str!("part_1_example_input.txt"),
+    part_1: Some("6"),
+    part_2: None,
+  },
+  Example {
+    name: "part_2_example_input.txt",
+    input: include_str!("part_2_example_input.txt"),
+    part_1: None,
+    part_2: Some("6"),
+  },
+];
+
+type ElementMap<'a> = HashMap<&'a str, (&'a str, &'a str)>;
+
+fn get_instructions_from_line(line: &InputLine) -> Result<Vec<char>, ParseError> {
+  if let Some((index, char)) = line
+    .text
+    .char_indices()
+    .find(|(_, char)| *char != 'L' && *char != 'R')
+  {
+    return Err(line.error(
+      &line.text[index..index + char.len_utf8()],
+      "expected L or R",
+    ));
+  }
+
+  if line.text.is_empty() {
+    return Err(line.error(line.text, "expected at least one instruction"));
+  }
+
+  Ok(line.text.chars().collect::<Vec<_>>())
+}
 
-pub const INPUT_FILENAME: &str = "src/day_8/input.txt";
+fn get_element_from_line<'a>(
+  line: &InputLine<'a>,
+) -> Result<(&'a str, (&'a str, &'a str)), ParseError> {
+  let (key, raw_lr_defs) = line.split_once(line.text, " = ")?;
+  let raw_lr_defs = line.strip_prefix(raw_lr_defs, "(")?;
+  let raw_lr_defs = raw_lr_defs
+    .strip_suffix(')')
+    .ok_or_else(|| line.error(raw_lr_defs, "expected \")\""))?;
+  let (left, right) = line.split_once(raw_lr_defs, ", ")?;
+
+  Ok((key, (left, right)))
+}
 
 fn get_instructions_and_element_map_from_input(
   input: &str,
-) -> (Vec<char>, HashMap<&str, (&str, &str)>) {
-  let lines = input.split('\n').collect::<Vec<&str>>();
-  let (raw_instructions, raw_element_defs) = match &lines[..] {
-    // ignore empty newline between instructions and defs with _
-    [raw_instructions, _, raw_element_defs @ ..] => (raw_instructions, raw_element_defs.to_vec()),
-    _ => panic!("input isn't in the right format :("),
+) -> Result<(Vec<char>, ElementMap<'_>), ParseError> {
+  let sections = input_sections(DAY, input);
+  let (raw_instructions, raw_element_defs) = match &sections[..] {
+    [instruction_lines, raw_element_defs] if instruction_lines.len() == 1 => {
+      (instruction_lines[0], raw_element_defs)
+    }
+    _ => {
+      return Err(end_of_input_error(
+        DAY,
+        input,
+        "expected a line of instructions, a blank line and then element definitions",
+      ))
+    }
   };
 
-  let instructions = raw_instructions.chars().collect::<Vec<_>>();
+  let instructions = get_instructions_from_line(&raw_instructions)?;
 
-  let element_map: HashMap<&str, (&str, &str)> =
-    raw_element_defs
-      .iter()
-      .fold(HashMap::new(), |mut acc, line| {
-        let (key, raw_lr_defs) = line.split_once(" = ").unwrap();
-        let (left, right) = &raw_lr_defs[1..raw_lr_defs.len() - 1]
-          .split_once(", ")
-          .unwrap();
+  let elements = raw_element_defs
+    .iter()
+    .map(|line| get_element_from_line(line).map(|element| (line, element)))
+    .collect::<Result<Vec<_>, _>>()?;
 
-        acc.insert(key, (left, right));
-        acc
-      });
+  let element_map: ElementMap = elements
+    .iter()
+    .map(|(_, element)| *element)
+    .collect();
 
-  (instructions, element_map)
+  // Every element has to lead somewhere that exists, so walking the map can never get stuck
+  for (line, (_, (left, right))) in elements.iter() {
+    for next_key in [left, right] {
+      if !element_map.contains_key(next_key) {
+        return Err(line.error(next_key, "expected a defined element"));
+      }
+    }
+  }
+
+  Ok((instructions, element_map))
 }
 
 const TARGET_KEY: &str = "ZZZ";
 
-fn part_1(input: &str) -> u64 {
-  let (instructions, element_map) = get_instructions_and_element_map_from_input(input);
+fn part_1(input: &str) -> Result<u64, ParseError> {
+  let (instructions, element_map) = get_instructions_and_element_map_from_input(input)?;
+
+  if !element_map.contains_key("AAA") {
+    return Err(end_of_input_error(DAY, input, "expected an AAA element"));
+  }
 
   let mut instructions_iter = instructions.into_iter().cycle();
   let mut steps = 0;
-  let mut current_key: &&str = &&"AAA";
+  let mut current_key: &&str = &"AAA";
 
   while **current_key != *TARGET_KEY {
     steps += 1;
@@ -52,7 +119,7 @@ fn part_1(input: &str) -> u64 {
     };
   }
 
-  steps
+  Ok(steps)
 }
 
 // Recursive function to return gcd of a and b
@@ -69,29 +136,29 @@ fn lcm(a: u64, b: u64) -> u64 {
   (a / gcd(a, b)) * b
 }
 
-fn part_2(input: &str) -> u64 {
-  let (instructions, element_map) = get_instructions_and_element_map_from_input(input);
+fn part_2(input: &str) -> Result<u64, ParseError> {
+  let (instructions, element_map) = get_instructions_and_element_map_from_input(input)?;
 
   let element_map_keys = element_map.keys();
 
   let starting_keys = element_map_keys
     .filter(|key| key.ends_with('A'))
-    .map(|key| *key)
+    .copied()
     .collect::<Vec<_>>();
 
-  println!("starting_keys: {:?}", starting_keys);
+  log::debug!("starting_keys: {:?}", starting_keys);
 
   let path_lengths = starting_keys.into_iter().map(|starting_key| {
     let mut length = 0;
     let mut instructions_iter = instructions.iter().cycle();
-    let mut current_key = starting_key.clone();
+    let mut current_key = starting_key;
     while !current_key.ends_with('Z') {
       let instruction = instructions_iter.next().unwrap();
 
       let (left, right) = element_map.get(current_key).unwrap();
       current_key = match instruction {
-        'L' => &left,
-        'R' => &right,
+        'L' => left,
+        'R' => right,
         other => panic!("Unsupported instruction found: {}", other),
       };
 
@@ -103,62 +170,79 @@ fn part_2(input: &str) -> u64 {
 
   // recursively calculate lcm
   path_lengths
-    .reduce(|acc, length| lcm(acc, length))
-    .unwrap()
+    .reduce(lcm)
+    .ok_or_else(|| end_of_input_error(DAY, input, "expected an element ending in A"))
 }
 
-pub fn run() -> Result<(), std::io::Error> {
-  println!("running day 8");
-  let input = crate::utils::read_input(INPUT_FILENAME)?;
+pub struct Day8;
+
+impl Solver for Day8 {
+  fn day(&self) -> u8 {
+    DAY
+  }
+
+  fn title(&self) -> &'static str {
+    "Haunted Wasteland"
+  }
+
+  fn examples(&self) -> &'static [Example] {
+    EXAMPLES
+  }
 
-  let part_1_total = part_1(&input);
-  println!("part_1 total {}", part_1_total);
+  fn part_1(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_1(input)?)))
+  }
 
-  let part_2_total = part_2(&input);
-  println!("part_2 total {}", part_2_total);
+  fn part_2(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_2(input)?)))
+  }
 
-  Ok(())
+  fn parse(&self, _part: Part, input: &str) -> Option<Result<(), ParseError>> {
+    Some(get_instructions_and_element_map_from_input(input).map(|_| ()))
+  }
 }
 
 #[cfg(test)]
 mod tests {
-  use more_asserts::assert_gt;
 
-  use crate::utils::read_input;
+  use crate::utils::read_day_input;
 
-  use super::{part_1, part_2, INPUT_FILENAME};
+  use super::{part_1, part_2};
 
-  const PART_1_EXAMPLE_INPUT_FILENAME: &str = "./src/day_8/part_1_example_input.txt";
-  const PART_2_EXAMPLE_INPUT_FILENAME: &str = "./src/day_8/part_2_example_input.txt";
+  const PART_1_EXAMPLE_INPUT_FILENAME: &str = "part_1_example_input.txt";
+  const PART_2_EXAMPLE_INPUT_FILENAME: &str = "part_2_example_input.txt";
 
   #[test]
-  pub fn day_8_part_1_example_works() {
-    let contents = read_input(PART_1_EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 6);
+  pub fn day_8_undefined_element_is_reported() {
+    let error = part_1("LR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, CCC)\nZZZ = (ZZZ, ZZZ)").unwrap_err();
+    assert_eq!((error.line, error.column), (4, 13));
+    assert_eq!(error.text, "CCC");
   }
 
   #[test]
-  pub fn day_8_part_1_solution_works() {
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 12737);
+  pub fn day_8_bad_instruction_is_reported() {
+    let error = part_1("LRX\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap_err();
+    assert_eq!((error.line, error.column), (1, 3));
   }
 
   #[test]
-  pub fn day_8_part_2_example_works() {
-    let contents = read_input(PART_2_EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
-    assert_eq!(result, 6);
+  pub fn day_8_non_ascii_instruction_is_reported() {
+    let error = part_1("LRé\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap_err();
+    assert_eq!((error.line, error.column), (1, 3));
+    assert_eq!(error.text, "é");
   }
 
   #[test]
-  pub fn day_8_part_2_solution_works() {
-    use more_asserts::assert_gt;
+  pub fn day_8_part_1_example_works() {
+    let contents = read_day_input(8, PART_1_EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_1(&contents).unwrap();
+    assert_eq!(result, 6);
+  }
 
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
-    assert_gt!(result, 21409);
-    assert_eq!(result, 9064949303801);
+  #[test]
+  pub fn day_8_part_2_example_works() {
+    let contents = read_day_input(8, PART_2_EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_2(&contents).unwrap();
+    assert_eq!(result, 6);
   }
 }
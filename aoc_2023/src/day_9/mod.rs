@@ -1,6 +1,18 @@
-const INPUT_FILENAME: &str = "./src/day_9/input.txt";
+use std::io::BufRead;
 
-fn tuple_vec_from_vec(input: &Vec<i32>) -> Vec<(i32, i32)> {
+use crate::parse::{input_lines, InputLine, LineReader, Lines, ParseError};
+use crate::solver::{Answer, Example, Part, PartResult, SolveError, Solver};
+
+const DAY: u8 = 9;
+
+const EXAMPLES: &[Example] = &[Example {
+  name: "example_input.txt",
+  input: include_str!("example_input.txt"),
+  part_1: Some("114"),
+  part_2: Some("2"),
+}];
+
+fn tuple_vec_from_vec(input: &[i64]) -> Vec<(i64, i64)> {
   input
     .iter()
     .enumerate()
@@ -13,118 +25,192 @@ fn tuple_vec_from_vec(input: &Vec<i32>) -> Vec<(i32, i32)> {
     })
 }
 
-fn find_next_value(numbers: &Vec<i32>) -> i32 {
-  let differences = tuple_vec_from_vec(&numbers)
+// None if the value or any of the differences leading to it overflows an i64
+fn find_next_value(numbers: &[i64]) -> Option<i64> {
+  let differences = tuple_vec_from_vec(numbers)
     .into_iter()
-    .map(|(a, b)| b - a)
-    .collect::<Vec<_>>();
+    .map(|(a, b)| b.checked_sub(a))
+    .collect::<Option<Vec<_>>>()?;
 
-  let differences_are_zero = differences.iter().find(|num| **num != 0) == None;
+  let differences_are_zero = differences
+    .iter()
+    .find(|num| **num != 0)
+    .is_none();
 
   let next_below_value = if differences_are_zero {
     0
   } else {
-    find_next_value(&differences)
+    find_next_value(&differences)?
   };
 
-  let result = numbers.last().unwrap() + next_below_value;
-  println!("result: {}", result);
-  result
+  let result = numbers
+    .last()
+    .unwrap()
+    .checked_add(next_below_value)?;
+  log::trace!("result: {}", result);
+  Some(result)
 }
 
-fn find_previous_value(numbers: &Vec<i32>) -> i32 {
-  let differences = tuple_vec_from_vec(&numbers)
+fn find_previous_value(numbers: &[i64]) -> Option<i64> {
+  let differences = tuple_vec_from_vec(numbers)
     .into_iter()
-    .map(|(a, b)| b - a)
-    .collect::<Vec<_>>();
+    .map(|(a, b)| b.checked_sub(a))
+    .collect::<Option<Vec<_>>>()?;
 
-  let differences_are_zero = differences.iter().find(|num| **num != 0) == None;
+  let differences_are_zero = differences
+    .iter()
+    .find(|num| **num != 0)
+    .is_none();
 
   let previous_below_value = if differences_are_zero {
     0
   } else {
-    find_previous_value(&differences)
+    find_previous_value(&differences)?
   };
 
-  let result = numbers.first().unwrap() - previous_below_value;
-  println!("result: {}", result);
-  result
+  let result = numbers
+    .first()
+    .unwrap()
+    .checked_sub(previous_below_value)?;
+  log::trace!("result: {}", result);
+  Some(result)
 }
 
-fn part_1(input: &str) -> i32 {
-  input
-    .split('\n')
-    .map(|line| {
-      line
-        .split(' ')
-        .map(|num_string| num_string.parse::<i32>().unwrap())
-        .collect::<Vec<_>>()
-    })
-    .map(|numbers| find_next_value(&numbers))
-    .reduce(|acc, result| acc + result)
-    .unwrap()
+fn numbers_from_line(line: &InputLine) -> Result<Vec<i64>, ParseError> {
+  if line.text.is_empty() {
+    return Err(line.error(line.text, "expected at least one number"));
+  }
+
+  line
+    .text
+    .split(' ')
+    .map(|num_string| line.parse::<i64>(num_string, "a number"))
+    .collect()
 }
 
-fn part_2(input: &str) -> i32 {
-  input
-    .split('\n')
-    .map(|line| {
-      line
-        .split(' ')
-        .map(|num_string| num_string.parse::<i32>().unwrap())
-        .collect::<Vec<_>>()
-    })
-    .map(|numbers| find_previous_value(&numbers))
-    .reduce(|acc, result| acc + result)
-    .unwrap()
+// Adds a line's extrapolated value to the running total, reporting the line if either
+// overflows
+fn add_extrapolated_value(
+  acc: i64,
+  line: &InputLine,
+  extrapolate: fn(&[i64]) -> Option<i64>,
+) -> Result<i64, SolveError> {
+  let numbers = numbers_from_line(line)?;
+
+  extrapolate(&numbers)
+    .ok_or_else(|| {
+      SolveError::overflow(
+        DAY,
+        line.number,
+        "the extrapolated value doesn't fit in 64 bits",
+      )
+    })?
+    .checked_add(acc)
+    .ok_or_else(|| SolveError::overflow(DAY, line.number, "the total doesn't fit in 64 bits"))
+}
+
+fn part_1(lines: impl Lines) -> Result<i64, SolveError> {
+  lines.try_fold_lines(DAY, 0, |acc, line| {
+    add_extrapolated_value(acc, &line, find_next_value)
+  })
+}
+
+fn part_2(lines: impl Lines) -> Result<i64, SolveError> {
+  lines.try_fold_lines(DAY, 0, |acc, line| {
+    add_extrapolated_value(acc, &line, find_previous_value)
+  })
 }
 
-pub fn run() -> Result<(), std::io::Error> {
-  println!("running day 9");
-  let input = crate::utils::read_input(INPUT_FILENAME)?;
+pub struct Day9;
+
+impl Solver for Day9 {
+  fn day(&self) -> u8 {
+    DAY
+  }
+
+  fn title(&self) -> &'static str {
+    "Mirage Maintenance"
+  }
+
+  fn examples(&self) -> &'static [Example] {
+    EXAMPLES
+  }
 
-  let part_1_total = part_1(&input);
-  println!("part_1 total {}", part_1_total);
+  fn part_1(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_1(input)?)))
+  }
 
-  let part_2_total = part_2(&input);
-  println!("part_2 total {}", part_2_total);
+  fn part_2(&self, input: &str) -> PartResult {
+    Ok(Some(Answer::from(part_2(input)?)))
+  }
 
-  Ok(())
+  fn solve_lines(&self, part: Part, reader: &mut dyn BufRead) -> PartResult {
+    let lines = LineReader::new(reader);
+    let result = match part {
+      Part::One => part_1(lines)?,
+      Part::Two => part_2(lines)?,
+    };
+    Ok(Some(Answer::from(result)))
+  }
+
+  fn parse(&self, _part: Part, input: &str) -> Option<Result<(), ParseError>> {
+    Some(input_lines(DAY, input).try_for_each(|line| numbers_from_line(&line).map(|_| ())))
+  }
 }
 
 #[cfg(test)]
 mod tests {
-  use crate::utils::read_input;
+  use crate::solver::SolveError;
+  use crate::utils::read_day_input;
 
-  use super::{part_1, part_2, INPUT_FILENAME};
+  use super::{part_1, part_2};
 
-  const EXAMPLE_INPUT_FILENAME: &str = "./src/day_9/example_input.txt";
+  const EXAMPLE_INPUT_FILENAME: &str = "example_input.txt";
 
   #[test]
-  pub fn day_9_part_1_example_works() {
-    let contents = read_input(EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 114);
+  pub fn day_9_bad_number_is_reported() {
+    match part_1("0 3 6 9\n1 3 x 10").unwrap_err() {
+      SolveError::Parse(error) => {
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.message, "expected a number");
+      }
+      other => panic!("unexpected error: {:?}", other),
+    }
   }
 
   #[test]
-  pub fn day_9_part_1_solution_works() {
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_1(&contents);
-    assert_eq!(result, 2043183816);
+  pub fn day_9_large_values_do_not_wrap() {
+    let result = part_1("2000000000 2100000000 2200000000").unwrap();
+    assert_eq!(result, 2300000000);
   }
 
   #[test]
-  pub fn day_9_part_2_example_works() {
-    let contents = read_input(EXAMPLE_INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
-    assert_eq!(result, 2);
+  pub fn day_9_overflowing_values_are_reported() {
+    assert_eq!(
+      part_1("0 1\n0 9223372036854775807").unwrap_err(),
+      SolveError::overflow(9, 2, "the extrapolated value doesn't fit in 64 bits")
+    );
+    assert_eq!(
+      part_2("0 0\n-9223372036854775807 1").unwrap_err(),
+      SolveError::overflow(9, 2, "the extrapolated value doesn't fit in 64 bits")
+    );
+    assert_eq!(
+      part_1("9223372036854775807\n1").unwrap_err(),
+      SolveError::overflow(9, 2, "the total doesn't fit in 64 bits")
+    );
+  }
+
+  #[test]
+  pub fn day_9_part_1_example_works() {
+    let contents = read_day_input(9, EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_1(&contents).unwrap();
+    assert_eq!(result, 114);
   }
 
   #[test]
-  pub fn day_9_part_2_solution_works() {
-    let contents = read_input(INPUT_FILENAME).unwrap();
-    let result = part_2(&contents);
-    assert_eq!(result, 1118);
+  pub fn day_9_part_2_example_works() {
+    let contents = read_day_input(9, EXAMPLE_INPUT_FILENAME).unwrap();
+    let result = part_2(&contents).unwrap();
+    assert_eq!(result, 2);
   }
 }
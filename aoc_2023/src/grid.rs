@@ -0,0 +1,369 @@
+use std::ops::{Index, IndexMut};
+
+use crate::parse::{input_lines, ParseError};
+
+// A 0-based position in a grid, x being the column and y the row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position {
+  pub x: usize,
+  pub y: usize,
+}
+
+impl Position {
+  pub fn new(x: usize, y: usize) -> Position {
+    Position { x, y }
+  }
+}
+
+// The smallest rectangle holding a group of cells, with both corners inside it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox {
+  pub min: Position,
+  pub max: Position,
+}
+
+impl BoundingBox {
+  pub fn contains(&self, position: Position) -> bool {
+    (self.min.x..=self.max.x).contains(&position.x)
+      && (self.min.y..=self.max.y).contains(&position.y)
+  }
+
+  // Row by row
+  pub fn positions(&self) -> impl Iterator<Item = Position> {
+    let BoundingBox { min, max } = *self;
+    (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Position::new(x, y)))
+  }
+}
+
+// Single steps as (dx, dy), for Grid::offset
+pub const NORTH: (isize, isize) = (0, -1);
+pub const EAST: (isize, isize) = (1, 0);
+pub const SOUTH: (isize, isize) = (0, 1);
+pub const WEST: (isize, isize) = (-1, 0);
+
+// Orthogonal neighbours, clockwise from north
+const OFFSETS_4: [(isize, isize); 4] = [NORTH, EAST, SOUTH, WEST];
+
+// Every surrounding cell, row by row from the top left
+const OFFSETS_8: [(isize, isize); 8] = [
+  (-1, -1),
+  (0, -1),
+  (1, -1),
+  (-1, 0),
+  (1, 0),
+  (-1, 1),
+  (0, 1),
+  (1, 1),
+];
+
+// A rectangular grid of cells, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+  cells: Vec<T>,
+  width: usize,
+  height: usize,
+}
+
+impl<T> Grid<T> {
+  // None unless there is exactly one cell per position
+  pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Option<Grid<T>> {
+    if cells.len() != width * height {
+      return None;
+    }
+
+    Some(Grid {
+      cells,
+      width,
+      height,
+    })
+  }
+
+  pub fn filled(width: usize, height: usize, value: T) -> Grid<T>
+  where
+    T: Clone,
+  {
+    Grid {
+      cells: vec![value; width * height],
+      width,
+      height,
+    }
+  }
+
+  // One cell per character, with every row as wide as the first. `cell` returns None for
+  // characters that aren't allowed, which are reported as "expected {expected}"
+  pub fn parse(
+    day: u8,
+    input: &str,
+    expected: &str,
+    mut cell: impl FnMut(char) -> Option<T>,
+  ) -> Result<Grid<T>, ParseError> {
+    let mut cells = Vec::new();
+    let mut width = None;
+    let mut height = 0;
+
+    for line in input_lines(day, input) {
+      let line_width = line.text.chars().count();
+      let width = *width.get_or_insert(line_width);
+
+      if line_width != width {
+        return Err(line.error(
+          line.text,
+          format!("expected a row {} characters wide", width),
+        ));
+      }
+
+      for (index, char) in line.text.char_indices() {
+        let value = cell(char).ok_or_else(|| {
+          line.error(
+            &line.text[index..index + char.len_utf8()],
+            format!("expected {}", expected),
+          )
+        })?;
+        cells.push(value);
+      }
+
+      height += 1;
+    }
+
+    Ok(Grid {
+      cells,
+      width: width.unwrap_or(0),
+      height,
+    })
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn len(&self) -> usize {
+    self.cells.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.cells.is_empty()
+  }
+
+  pub fn contains(&self, position: Position) -> bool {
+    position.x < self.width && position.y < self.height
+  }
+
+  // Index into the row-by-row cells, for callers that keep their own flat per-cell data
+  pub fn index_of(&self, position: Position) -> Option<usize> {
+    self
+      .contains(position)
+      .then(|| position.y * self.width + position.x)
+  }
+
+  pub fn position_of(&self, index: usize) -> Option<Position> {
+    (index < self.cells.len()).then(|| Position::new(index % self.width, index / self.width))
+  }
+
+  pub fn get(&self, position: Position) -> Option<&T> {
+    self
+      .index_of(position)
+      .map(|index| &self.cells[index])
+  }
+
+  pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+    self
+      .index_of(position)
+      .map(|index| &mut self.cells[index])
+  }
+
+  // The position `dx` columns and `dy` rows away, if that is still inside the grid
+  pub fn offset(&self, position: Position, dx: isize, dy: isize) -> Option<Position> {
+    let x = position.x.checked_add_signed(dx)?;
+    let y = position.y.checked_add_signed(dy)?;
+    let position = Position::new(x, y);
+
+    self.contains(position).then_some(position)
+  }
+
+  pub fn neighbours_4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+    OFFSETS_4
+      .iter()
+      .filter_map(move |(dx, dy)| self.offset(position, *dx, *dy))
+  }
+
+  pub fn neighbours_8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+    OFFSETS_8
+      .iter()
+      .filter_map(move |(dx, dy)| self.offset(position, *dx, *dy))
+  }
+
+  // Cells touching the box from outside, including diagonally, row by row
+  pub fn surrounding(&self, bounds: BoundingBox) -> impl Iterator<Item = Position> {
+    let min = Position::new(
+      bounds.min.x.saturating_sub(1),
+      bounds.min.y.saturating_sub(1),
+    );
+    let max = Position::new(
+      (bounds.max.x + 1).min(self.width.saturating_sub(1)),
+      (bounds.max.y + 1).min(self.height.saturating_sub(1)),
+    );
+
+    BoundingBox { min, max }
+      .positions()
+      .filter(move |position| !bounds.contains(*position))
+  }
+
+  pub fn row(&self, y: usize) -> Option<&[T]> {
+    (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+  }
+
+  pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+    (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+  }
+
+  pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+    (x < self.width).then(|| self.cells.iter().skip(x).step_by(self.width))
+  }
+
+  pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+    (0..self.width).map(|x| self.cells.iter().skip(x).step_by(self.width))
+  }
+
+  // Every cell with its position, row by row
+  pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+    let width = self.width;
+
+    self
+      .cells
+      .iter()
+      .enumerate()
+      .map(move |(index, cell)| (Position::new(index % width, index / width), cell))
+  }
+
+  // The first matching cell, row by row
+  pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<Position> {
+    self
+      .cells
+      .iter()
+      .position(predicate)
+      .and_then(|index| self.position_of(index))
+  }
+
+  pub fn positions(&self) -> impl Iterator<Item = Position> {
+    let width = self.width;
+    (0..self.cells.len()).map(move |index| Position::new(index % width, index / width))
+  }
+
+  pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+    Grid {
+      cells: self.cells.iter().map(f).collect(),
+      width: self.width,
+      height: self.height,
+    }
+  }
+}
+
+// Indexing panics outside the grid, use `get` when that's possible
+impl<T> Index<Position> for Grid<T> {
+  type Output = T;
+
+  fn index(&self, position: Position) -> &T {
+    self.get(position).unwrap_or_else(|| {
+      panic!(
+        "{:?} is outside a {}x{} grid",
+        position, self.width, self.height
+      )
+    })
+  }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+  fn index_mut(&mut self, position: Position) -> &mut T {
+    let (width, height) = (self.width, self.height);
+
+    self
+      .get_mut(position)
+      .unwrap_or_else(|| panic!("{:?} is outside a {}x{} grid", position, width, height))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{BoundingBox, Grid, Position};
+
+  fn digits() -> Grid<u32> {
+    Grid::parse(1, "123\n456\n", "a digit", |char| char.to_digit(10)).unwrap()
+  }
+
+  #[test]
+  pub fn grids_parse_row_by_row() {
+    let grid = digits();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid.get(Position::new(2, 1)), Some(&6));
+    assert_eq!(grid.get(Position::new(3, 0)), None);
+    assert_eq!(grid.get(Position::new(0, 2)), None);
+    assert_eq!(grid[Position::new(1, 0)], 2);
+  }
+
+  #[test]
+  pub fn ragged_rows_and_unexpected_characters_are_reported() {
+    let error = Grid::parse(1, "123\n45\n", "a digit", |char| char.to_digit(10)).unwrap_err();
+    assert_eq!((error.line, error.column), (2, 1));
+    assert_eq!(error.message, "expected a row 3 characters wide");
+
+    let error = Grid::parse(1, "123\n4x6\n", "a digit", |char| char.to_digit(10)).unwrap_err();
+    assert_eq!((error.line, error.column), (2, 2));
+    assert_eq!(error.message, "expected a digit");
+  }
+
+  #[test]
+  pub fn neighbours_stay_inside_the_grid() {
+    let grid = digits();
+    let corner = Position::new(0, 0);
+    let values = |positions: Vec<Position>| {
+      positions
+        .into_iter()
+        .map(|position| grid[position])
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(values(grid.neighbours_4(corner).collect()), vec![2, 4]);
+    assert_eq!(values(grid.neighbours_8(corner).collect()), vec![2, 4, 5]);
+    assert_eq!(
+      values(grid.neighbours_8(Position::new(1, 1)).collect()),
+      vec![1, 2, 3, 4, 6]
+    );
+  }
+
+  #[test]
+  pub fn surrounding_cells_are_clipped_to_the_grid() {
+    let grid = digits();
+    let bounds = BoundingBox {
+      min: Position::new(0, 0),
+      max: Position::new(1, 0),
+    };
+    let values = grid
+      .surrounding(bounds)
+      .map(|position| grid[position])
+      .collect::<Vec<_>>();
+
+    assert_eq!(values, vec![3, 4, 5, 6]);
+  }
+
+  #[test]
+  pub fn rows_and_columns_iterate_in_order() {
+    let grid = digits();
+    assert_eq!(
+      grid.rows().collect::<Vec<_>>(),
+      vec![&[1, 2, 3], &[4, 5, 6]]
+    );
+    assert_eq!(
+      grid
+        .columns()
+        .map(|column| column.copied().collect::<Vec<_>>())
+        .collect::<Vec<_>>(),
+      vec![vec![1, 4], vec![2, 5], vec![3, 6]]
+    );
+    assert_eq!(grid.find(|value| *value > 4), Some(Position::new(1, 1)));
+    assert_eq!(grid.find(|value| *value > 6), None);
+  }
+}
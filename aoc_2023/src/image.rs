@@ -0,0 +1,217 @@
+use std::fmt::Write;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+use crate::grid::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+  // Binary portable pixmap, one block of pixels per cell
+  Ppm,
+  // Scalable vector graphics, one unit per cell
+  Svg,
+}
+
+impl ImageFormat {
+  // Guessed from a path's extension
+  pub fn from_path(path: &Path) -> Option<ImageFormat> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+
+    match extension.as_str() {
+      "ppm" => Some(ImageFormat::Ppm),
+      "svg" => Some(ImageFormat::Svg),
+      _ => None,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+  pub fn hex(&self) -> String {
+    format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+  }
+}
+
+// PPMs are written uncompressed at 3 bytes a pixel, so this keeps them under 200MB
+pub const MAX_PPM_PIXELS: usize = 64 * 1024 * 1024;
+
+// Binary PPM, with every pixel of the grid drawn as a `scale` by `scale` block. Refused if the
+// result would have more than MAX_PPM_PIXELS pixels
+pub fn ppm(pixels: &Grid<Rgb>, scale: usize) -> Result<Vec<u8>, Error> {
+  let width = pixels.width().saturating_mul(scale);
+  let height = pixels.height().saturating_mul(scale);
+
+  if width.saturating_mul(height) > MAX_PPM_PIXELS {
+    return Err(Error::new(
+      ErrorKind::InvalidInput,
+      format!(
+        "a {} by {} pixel image is too large, PPMs are limited to {} pixels",
+        width, height, MAX_PPM_PIXELS
+      ),
+    ));
+  }
+
+  let mut output = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+  output.reserve(width * height * 3);
+
+  for row in pixels.rows() {
+    let scaled_row = row
+      .iter()
+      .flat_map(|Rgb(red, green, blue)| [*red, *green, *blue].repeat(scale))
+      .collect::<Vec<_>>();
+
+    for _ in 0..scale {
+      output.extend_from_slice(&scaled_row);
+    }
+  }
+
+  Ok(output)
+}
+
+// An SVG document measured in cells, so shapes are placed by column and row. `scale` only sets
+// how big it's shown by default
+pub struct Svg {
+  width: usize,
+  height: usize,
+  scale: usize,
+  body: String,
+}
+
+impl Svg {
+  pub fn new(width: usize, height: usize, scale: usize, background: Rgb) -> Svg {
+    let mut svg = Svg {
+      width,
+      height,
+      scale,
+      body: String::new(),
+    };
+    svg.rect(0.0, 0.0, width as f64, height as f64, background);
+    svg
+  }
+
+  pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, fill: Rgb) {
+    let _ = writeln!(
+      self.body,
+      "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+      x,
+      y,
+      width,
+      height,
+      fill.hex()
+    );
+  }
+
+  pub fn circle(&mut self, x: f64, y: f64, radius: f64, fill: Rgb) {
+    let _ = writeln!(
+      self.body,
+      "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>",
+      x,
+      y,
+      radius,
+      fill.hex()
+    );
+  }
+
+  // A closed outline through every point in order
+  pub fn polygon(&mut self, points: &[(f64, f64)], stroke: Rgb, stroke_width: f64) {
+    let points = points
+      .iter()
+      .map(|(x, y)| format!("{},{}", x, y))
+      .collect::<Vec<_>>()
+      .join(" ");
+
+    let _ = writeln!(
+      self.body,
+      "<polygon points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" stroke-linejoin=\"round\"/>",
+      points,
+      stroke.hex(),
+      stroke_width
+    );
+  }
+
+  pub fn finish(self) -> String {
+    format!(
+      "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">\n{}</svg>\n",
+      self.width * self.scale,
+      self.height * self.scale,
+      self.width,
+      self.height,
+      self.body
+    )
+  }
+}
+
+// Helpers for the tests of the days that export images
+#[cfg(test)]
+pub mod test_support {
+  use super::Rgb;
+
+  // The colour at a pixel of a binary PPM that's `width` by `height` pixels
+  pub fn pixel(image: &[u8], width: usize, height: usize, x: usize, y: usize) -> Rgb {
+    let header = format!("P6\n{} {}\n255\n", width, height).len();
+    let offset = header + (y * width + x) * 3;
+    Rgb(image[offset], image[offset + 1], image[offset + 2])
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::ErrorKind;
+  use std::path::Path;
+
+  use super::{ppm, ImageFormat, Rgb, Svg};
+  use crate::grid::Grid;
+
+  #[test]
+  pub fn ppm_pixels_are_scaled_up() {
+    let black = Rgb(0, 0, 0);
+    let white = Rgb(255, 255, 255);
+    let pixels = Grid::from_cells(2, 1, vec![black, white]).unwrap();
+
+    let mut expected = b"P6\n4 2\n255\n".to_vec();
+    for _ in 0..2 {
+      expected.extend_from_slice(&[0, 0, 0, 0, 0, 0, 255, 255, 255, 255, 255, 255]);
+    }
+
+    assert_eq!(ppm(&pixels, 2).unwrap(), expected);
+  }
+
+  #[test]
+  pub fn oversized_ppms_are_refused() {
+    let pixels = Grid::filled(140, 140, Rgb(0, 0, 0));
+
+    let error = ppm(&pixels, 3 * 64).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    assert!(error
+      .to_string()
+      .starts_with("a 26880 by 26880 pixel image is too large"));
+    assert!(ppm(&Grid::filled(1, 1, Rgb(0, 0, 0)), usize::MAX).is_err());
+  }
+
+  #[test]
+  pub fn svg_is_measured_in_cells() {
+    let mut svg = Svg::new(3, 2, 10, Rgb(0, 0, 0));
+    svg.rect(1.0, 0.0, 2.0, 1.0, Rgb(255, 0, 16));
+    let svg = svg.finish();
+
+    assert!(svg.starts_with(
+      "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"30\" height=\"20\" viewBox=\"0 0 3 2\""
+    ));
+    assert!(svg.contains("<rect x=\"1\" y=\"0\" width=\"2\" height=\"1\" fill=\"#ff0010\"/>"));
+  }
+
+  #[test]
+  pub fn formats_are_guessed_from_extensions() {
+    assert_eq!(
+      ImageFormat::from_path(Path::new("day_3.SVG")),
+      Some(ImageFormat::Svg)
+    );
+    assert_eq!(
+      ImageFormat::from_path(Path::new("out/day_10.ppm")),
+      Some(ImageFormat::Ppm)
+    );
+    assert_eq!(ImageFormat::from_path(Path::new("day_10.png")), None);
+  }
+}
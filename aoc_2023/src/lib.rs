@@ -0,0 +1,20 @@
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod day_1;
+pub mod day_10;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+pub mod grid;
+pub mod image;
+pub mod logging;
+pub mod parse;
+pub mod runner;
+pub mod solver;
+pub mod utils;
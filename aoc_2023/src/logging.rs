@@ -0,0 +1,173 @@
+use std::io::Write;
+use std::str::FromStr;
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+// Targets are module paths, shown and matched without the crate name so that `day_5` refers to
+// everything logged from the day_5 module
+const CRATE_PREFIX: &str = concat!(env!("CARGO_CRATE_NAME"), "::");
+
+// Warnings and errors are shown by default. Each -v adds a level (info, debug, then trace) and
+// each -q removes one, down to nothing at all
+pub fn level_from_verbosity(verbose: u8, quiet: u8) -> LevelFilter {
+  match 2 + verbose as i16 - quiet as i16 {
+    i16::MIN..=0 => LevelFilter::Off,
+    1 => LevelFilter::Error,
+    2 => LevelFilter::Warn,
+    3 => LevelFilter::Info,
+    4 => LevelFilter::Debug,
+    _ => LevelFilter::Trace,
+  }
+}
+
+// A `--log` argument, either a bare target like `day_7` (which enables everything it logs) or
+// `target=level` like `day_5=debug`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetFilter {
+  pub target: String,
+  pub level: LevelFilter,
+}
+
+impl FromStr for TargetFilter {
+  type Err = String;
+
+  fn from_str(spec: &str) -> Result<TargetFilter, String> {
+    let (target, level) = match spec.split_once('=') {
+      Some((target, level)) => (
+        target,
+        LevelFilter::from_str(level).map_err(|_| format!("unknown log level {:?}", level))?,
+      ),
+      None => (spec, LevelFilter::Trace),
+    };
+
+    if target.is_empty() {
+      return Err(format!("expected a log target, found {:?}", spec));
+    }
+
+    Ok(TargetFilter {
+      target: target.to_string(),
+      level,
+    })
+  }
+}
+
+fn short_target(target: &str) -> &str {
+  target
+    .strip_prefix(CRATE_PREFIX)
+    .unwrap_or(target)
+}
+
+// Writes records to stderr, so that stdout only ever holds results
+struct StderrLogger {
+  level: LevelFilter,
+  target_filters: Vec<TargetFilter>,
+}
+
+impl StderrLogger {
+  // The most specific matching target filter wins, otherwise the global level applies
+  fn level_for(&self, target: &str) -> LevelFilter {
+    let target = short_target(target);
+
+    self
+      .target_filters
+      .iter()
+      .filter(|filter| {
+        target == filter.target
+          || target
+            .strip_prefix(filter.target.as_str())
+            .is_some_and(|rest| rest.starts_with("::"))
+      })
+      .max_by_key(|filter| filter.target.len())
+      .map(|filter| filter.level)
+      .unwrap_or(self.level)
+  }
+}
+
+impl Log for StderrLogger {
+  fn enabled(&self, metadata: &Metadata) -> bool {
+    metadata.level() <= self.level_for(metadata.target())
+  }
+
+  fn log(&self, record: &Record) {
+    if !self.enabled(record.metadata()) {
+      return;
+    }
+
+    let _ = writeln!(
+      std::io::stderr(),
+      "{} {}: {}",
+      record.level(),
+      short_target(record.target()),
+      record.args()
+    );
+  }
+
+  fn flush(&self) {
+    let _ = std::io::stderr().flush();
+  }
+}
+
+pub fn init(level: LevelFilter, target_filters: Vec<TargetFilter>) {
+  let max_level = target_filters
+    .iter()
+    .map(|filter| filter.level)
+    .fold(level, std::cmp::max);
+
+  let logger = StderrLogger {
+    level,
+    target_filters,
+  };
+
+  if log::set_logger(Box::leak(Box::new(logger))).is_ok() {
+    log::set_max_level(max_level);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use log::LevelFilter;
+
+  use super::{level_from_verbosity, StderrLogger, TargetFilter};
+
+  #[test]
+  pub fn verbosity_flags_move_the_level() {
+    assert_eq!(level_from_verbosity(0, 0), LevelFilter::Warn);
+    assert_eq!(level_from_verbosity(1, 0), LevelFilter::Info);
+    assert_eq!(level_from_verbosity(3, 0), LevelFilter::Trace);
+    assert_eq!(level_from_verbosity(9, 0), LevelFilter::Trace);
+    assert_eq!(level_from_verbosity(0, 2), LevelFilter::Off);
+    assert_eq!(level_from_verbosity(1, 1), LevelFilter::Warn);
+  }
+
+  #[test]
+  pub fn target_filters_parse() {
+    assert_eq!(
+      "day_5=debug".parse::<TargetFilter>(),
+      Ok(TargetFilter {
+        target: String::from("day_5"),
+        level: LevelFilter::Debug,
+      })
+    );
+    assert_eq!(
+      "day_7".parse::<TargetFilter>().unwrap().level,
+      LevelFilter::Trace
+    );
+    assert!("day_5=loud".parse::<TargetFilter>().is_err());
+    assert!("=debug".parse::<TargetFilter>().is_err());
+  }
+
+  #[test]
+  pub fn most_specific_target_wins() {
+    let logger = StderrLogger {
+      level: LevelFilter::Warn,
+      target_filters: vec![
+        "day_1=debug".parse().unwrap(),
+        "day_10=info".parse().unwrap(),
+      ],
+    };
+
+    assert_eq!(logger.level_for("aoc_2023::day_1"), LevelFilter::Debug);
+    assert_eq!(logger.level_for("aoc_2023::day_10"), LevelFilter::Info);
+    assert_eq!(logger.level_for("aoc_2023::day_2"), LevelFilter::Warn);
+  }
+}
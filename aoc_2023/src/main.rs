@@ -1,47 +1,37 @@
-mod day_1;
-mod day_10;
-mod day_2;
-mod day_3;
-mod day_4;
-mod day_5;
-mod day_6;
-mod day_7;
-mod day_8;
-mod day_9;
-mod utils;
+use std::process::ExitCode;
 
-fn main() -> Result<(), std::io::Error> {
-  let args = std::env::args().collect::<Vec<_>>();
+use clap::Parser;
 
-  if args.len() == 2 {
-    match args[1].as_str() {
-      "day_1" => day_1::run()?,
-      "day_2" => day_2::run()?,
-      "day_3" => day_3::run()?,
-      "day_4" => day_4::run()?,
-      "day_5" => day_5::run()?,
-      "day_6" => day_6::run()?,
-      "day_7" => day_7::run()?,
-      "day_8" => day_8::run()?,
-      "day_9" => day_9::run()?,
-      "day_10" => day_10::run()?,
-      value => {
-        println!("{value} not recognized")
-      }
+use aoc_2023::cli::{Cli, Command};
+use aoc_2023::{logging, runner};
+
+fn main() -> ExitCode {
+  let cli = Cli::parse();
+
+  logging::init(
+    logging::level_from_verbosity(cli.log_args.verbose, cli.log_args.quiet),
+    cli.log_args.target_filters,
+  );
+
+  let result = match cli.command {
+    None => runner::run(&Default::default()),
+    Some(Command::Run(args)) => runner::run(&args),
+    Some(Command::List) => {
+      runner::list();
+      Ok(true)
     }
-  } else {
-    println!("Running all days");
-    day_1::run()?;
-    day_2::run()?;
-    day_3::run()?;
-    day_4::run()?;
-    day_5::run()?;
-    day_6::run()?;
-    day_7::run()?;
-    day_8::run()?;
-    day_9::run()?;
-    day_10::run()?;
+    Some(Command::Check(args)) => runner::check(&args),
+    Some(Command::Bench(args)) => runner::bench(&args),
+    Some(Command::Render(args)) => runner::render(&args),
+    Some(Command::Export(args)) => runner::export(&args),
   };
 
-  Ok(())
+  match result {
+    Ok(true) => ExitCode::SUCCESS,
+    Ok(false) => ExitCode::FAILURE,
+    Err(error) => {
+      eprintln!("error: {}", error);
+      ExitCode::FAILURE
+    }
+  }
 }
@@ -0,0 +1,343 @@
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+// Where and why a day's puzzle input couldn't be parsed. Lines and columns are 1-based
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+  pub day: u8,
+  pub line: usize,
+  pub column: usize,
+  pub text: String,
+  pub message: String,
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "day {} line {} column {}: {} (found {:?})",
+      self.day, self.line, self.column, self.message, self.text
+    )
+  }
+}
+
+impl std::error::Error for ParseError {}
+
+// A single line of puzzle input, numbered so that parse errors can point back at it
+#[derive(Debug, Clone, Copy)]
+pub struct InputLine<'a> {
+  pub day: u8,
+  pub number: usize,
+  pub text: &'a str,
+}
+
+impl<'a> InputLine<'a> {
+  // `offending` should be a slice of this line so the error can report its column
+  pub fn error(&self, offending: &str, message: impl Into<String>) -> ParseError {
+    ParseError {
+      day: self.day,
+      line: self.number,
+      column: self.column_of(offending),
+      text: offending.to_string(),
+      message: message.into(),
+    }
+  }
+
+  // 1-based column of a slice of this line. Anything that isn't part of the line points at
+  // the start of it
+  fn column_of(&self, slice: &str) -> usize {
+    let line_start = self.text.as_ptr() as usize;
+    let slice_start = slice.as_ptr() as usize;
+
+    if slice_start >= line_start && slice_start <= line_start + self.text.len() {
+      self.text[..slice_start - line_start]
+        .chars()
+        .count()
+        + 1
+    } else {
+      1
+    }
+  }
+
+  pub fn split_once(
+    &self,
+    text: &'a str,
+    delimiter: &str,
+  ) -> Result<(&'a str, &'a str), ParseError> {
+    text
+      .split_once(delimiter)
+      .ok_or_else(|| self.error(text, format!("expected {:?}", delimiter)))
+  }
+
+  pub fn strip_prefix(&self, text: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    text
+      .strip_prefix(prefix)
+      .ok_or_else(|| self.error(text, format!("expected {:?}", prefix)))
+  }
+
+  // `what` describes the expected value for the error message, e.g. "a cube count"
+  pub fn parse<T: FromStr>(&self, text: &'a str, what: &str) -> Result<T, ParseError> {
+    text
+      .parse::<T>()
+      .map_err(|_| self.error(text, format!("expected {}", what)))
+  }
+}
+
+// Every day reads its input through input_lines or LineReader, so that inputs saved by any editor
+// parse the same. Both split on \n and \r\n, and ignore a leading byte order mark, trailing
+// whitespace on each line and blank lines at the end of the input
+fn clean_line(number: usize, text: &str) -> &str {
+  let text = match number {
+    1 => text.strip_prefix('\u{feff}').unwrap_or(text),
+    _ => text,
+  };
+
+  text.trim_end()
+}
+
+pub fn input_lines(day: u8, input: &str) -> impl Iterator<Item = InputLine<'_>> {
+  let lines = input
+    .lines()
+    .enumerate()
+    .map(move |(index, text)| InputLine {
+      day,
+      number: index + 1,
+      text: clean_line(index + 1, text),
+    });
+
+  let line_count = lines
+    .clone()
+    .filter(|line| !line.text.is_empty())
+    .last()
+    .map_or(0, |line| line.number);
+
+  lines.take(line_count)
+}
+
+// Groups of lines separated by blank lines. Sections are never empty
+pub fn input_sections(day: u8, input: &str) -> Vec<Vec<InputLine<'_>>> {
+  let sections = input_lines(day, input).fold(vec![Vec::new()], |mut acc, line| {
+    if line.text.is_empty() {
+      acc.push(Vec::new());
+    } else {
+      acc.last_mut().unwrap().push(line);
+    }
+
+    acc
+  });
+
+  sections
+    .into_iter()
+    .filter(|section| !section.is_empty())
+    .collect()
+}
+
+// Line-oriented input that is folded over a line at a time, whether it's already in memory or
+// streamed from a reader. Lines that can't be read are reported through the fold's own error type
+pub trait Lines {
+  fn try_fold_lines<T, E: From<ParseError>>(
+    self,
+    day: u8,
+    init: T,
+    f: impl FnMut(T, InputLine<'_>) -> Result<T, E>,
+  ) -> Result<T, E>;
+}
+
+impl<S: AsRef<str> + ?Sized> Lines for &S {
+  fn try_fold_lines<T, E: From<ParseError>>(
+    self,
+    day: u8,
+    init: T,
+    f: impl FnMut(T, InputLine<'_>) -> Result<T, E>,
+  ) -> Result<T, E> {
+    input_lines(day, self.as_ref()).try_fold(init, f)
+  }
+}
+
+// Reads lines on demand so that only the current one is held in memory. Lines are cleaned up the
+// same way as input_lines, with blank lines held back until a non-blank line shows they aren't
+// at the end
+pub struct LineReader<R> {
+  reader: R,
+}
+
+impl<R: BufRead> LineReader<R> {
+  pub fn new(reader: R) -> LineReader<R> {
+    LineReader { reader }
+  }
+}
+
+impl<R: BufRead> Lines for LineReader<R> {
+  fn try_fold_lines<T, E: From<ParseError>>(
+    mut self,
+    day: u8,
+    init: T,
+    mut f: impl FnMut(T, InputLine<'_>) -> Result<T, E>,
+  ) -> Result<T, E> {
+    let mut buffer = String::new();
+    let mut acc = init;
+    let mut pending_blank_lines = 0;
+
+    for number in 1.. {
+      buffer.clear();
+      let bytes_read = self
+        .reader
+        .read_line(&mut buffer)
+        .map_err(|error| read_error(day, number, error))?;
+      if bytes_read == 0 {
+        break;
+      }
+
+      let text = clean_line(number, &buffer);
+      if text.is_empty() {
+        pending_blank_lines += 1;
+        continue;
+      }
+
+      for blank_number in number - pending_blank_lines..number {
+        acc = f(
+          acc,
+          InputLine {
+            day,
+            number: blank_number,
+            text: "",
+          },
+        )?;
+      }
+      pending_blank_lines = 0;
+
+      acc = f(acc, InputLine { day, number, text })?;
+    }
+
+    Ok(acc)
+  }
+}
+
+// Input that couldn't be read at all, including input that isn't valid UTF-8
+pub fn read_error(day: u8, line: usize, error: std::io::Error) -> ParseError {
+  ParseError {
+    day,
+    line,
+    column: 1,
+    text: String::new(),
+    message: format!("could not read input: {}", error),
+  }
+}
+
+// Errors that aren't tied to a single line, like a required line that is missing entirely,
+// point just past the end of the input
+pub fn end_of_input_error(day: u8, input: &str, message: impl Into<String>) -> ParseError {
+  ParseError {
+    day,
+    line: input_lines(day, input).count() + 1,
+    column: 1,
+    text: String::new(),
+    message: message.into(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::Cursor;
+
+  use super::{end_of_input_error, input_lines, input_sections, LineReader, Lines, ParseError};
+
+  fn collect_lines(lines: impl Lines) -> Vec<(usize, String)> {
+    lines
+      .try_fold_lines(1, Vec::new(), |mut acc, line| {
+        acc.push((line.number, line.text.to_string()));
+        Ok::<_, ParseError>(acc)
+      })
+      .unwrap()
+  }
+
+  #[test]
+  pub fn errors_point_at_the_offending_text() {
+    let line = input_lines(2, "first\nGame 1: x blue")
+      .nth(1)
+      .unwrap();
+    let (_, summary) = line.split_once(line.text, ": ").unwrap();
+    let (count, _) = line.split_once(summary, " ").unwrap();
+    let error = line
+      .parse::<u32>(count, "a cube count")
+      .unwrap_err();
+
+    assert_eq!(error.line, 2);
+    assert_eq!(error.column, 9);
+    assert_eq!(error.text, "x");
+    assert_eq!(
+      error.to_string(),
+      "day 2 line 2 column 9: expected a cube count (found \"x\")"
+    );
+  }
+
+  #[test]
+  pub fn missing_delimiter_is_reported() {
+    let line = input_lines(4, "Card 1 41 48 | 83 86")
+      .next()
+      .unwrap();
+    let error = line.split_once(line.text, ":").unwrap_err();
+
+    assert_eq!(error.column, 1);
+    assert_eq!(error.message, "expected \":\"");
+  }
+
+  #[test]
+  pub fn sections_are_split_on_blank_lines() {
+    let sections = input_sections(5, "a\n\nb\nc\n\n\nd\n\n");
+    let numbers = sections
+      .iter()
+      .map(|section| {
+        section
+          .iter()
+          .map(|line| line.number)
+          .collect::<Vec<_>>()
+      })
+      .collect::<Vec<_>>();
+
+    assert_eq!(numbers, vec![vec![1], vec![3, 4], vec![7]]);
+  }
+
+  #[test]
+  pub fn line_endings_and_trailing_blank_lines_are_ignored() {
+    let expected = vec![
+      (1, String::from("one")),
+      (2, String::from("two")),
+      (3, String::new()),
+      (4, String::from("three")),
+    ];
+
+    assert_eq!(collect_lines("one\ntwo\n\nthree"), expected);
+    assert_eq!(
+      collect_lines("\u{feff}one\r\ntwo  \r\n\r\nthree\r\n\r\n\n"),
+      expected
+    );
+    assert_eq!(collect_lines("three\r"), vec![(1, String::from("three"))]);
+  }
+
+  #[test]
+  pub fn streamed_lines_match_in_memory_lines() {
+    let input = "one\r\ntwo\n\n\nthree\n\r\n";
+    assert_eq!(
+      collect_lines(LineReader::new(Cursor::new(input))),
+      collect_lines(input)
+    );
+  }
+
+  #[test]
+  pub fn unreadable_lines_are_reported() {
+    let error = LineReader::new(Cursor::new(b"ok\n\xff\n".to_vec()))
+      .try_fold_lines(4, (), |_, _| Ok::<_, ParseError>(()))
+      .unwrap_err();
+    assert_eq!(error.line, 2);
+  }
+
+  #[test]
+  pub fn end_of_input_error_points_past_the_last_line() {
+    let error = end_of_input_error(6, "Time: 7 15 30", "expected a distance line");
+    assert_eq!(error.line, 2);
+    let error = end_of_input_error(6, "Time: 7 15 30\r\n\r\n", "expected a distance line");
+    assert_eq!(error.line, 2);
+  }
+}
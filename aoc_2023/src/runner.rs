@@ -0,0 +1,653 @@
+use std::io::{BufRead, Error, ErrorKind, IsTerminal};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::time::Duration;
+
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+use serde::Serialize;
+
+use crate::answers::{KnownAnswers, Verification};
+use crate::bench::{time, BenchRecord, Timings};
+use crate::cli::{
+  BenchArgs, CheckArgs, ColorChoice, ExportArgs, OutputFormat, RenderArgs, RunArgs, SolveArgs,
+};
+use crate::day_10::render::RenderOptions;
+use crate::day_10::{self, PipeMap};
+use crate::day_3::{self, Schematic};
+use crate::image::ImageFormat;
+use crate::solver::{find_solver, Answer, Part, PartResult, SolveError, Solver, SOLVERS};
+use crate::utils::{crate_input_dir, input_search_dirs, InputSource, INPUT_FILENAME};
+
+// Every solver the command applies to - a single day if --day was passed, otherwise all of them
+fn select_solvers(args: &SolveArgs) -> Result<Vec<&'static dyn Solver>, Error> {
+  match args.day {
+    Some(day) => match find_solver(day) {
+      Some(solver) => Ok(vec![solver]),
+      None => Err(Error::new(
+        ErrorKind::NotFound,
+        format!("day {} is not available", day),
+      )),
+    },
+    None => Ok(SOLVERS.to_vec()),
+  }
+}
+
+fn input_source(solver: &dyn Solver, args: &SolveArgs) -> Result<InputSource, Error> {
+  match &args.input_args.input {
+    Some(path) => Ok(InputSource::from_arg(path)),
+    None => {
+      let search_dirs = input_search_dirs(args.input_args.inputs_dir.as_deref());
+      InputSource::from_search_dirs(&search_dirs, solver.day(), INPUT_FILENAME)
+    }
+  }
+}
+
+fn example_source(solver: &dyn Solver, part: Part) -> Result<InputSource, Error> {
+  match solver.example_for(part) {
+    Some(example) => Ok(InputSource::Embedded {
+      name: format!("day_{}/{}", solver.day(), example.name),
+      contents: example.input,
+    }),
+    None => Err(Error::new(
+      ErrorKind::NotFound,
+      format!("day {} has no example input", solver.day()),
+    )),
+  }
+}
+
+fn read_source(source: &InputSource) -> Result<String, Error> {
+  log::info!("reading contents of {}", source);
+  source.read()
+}
+
+// Pairs each selected part with the input it should be solved against. Examples can differ
+// between parts, while the real input is only read once so that stdin works for both parts
+fn read_part_inputs(
+  solver: &dyn Solver,
+  args: &SolveArgs,
+) -> Result<Vec<(Part, InputSource, String)>, Error> {
+  if args.input_args.example {
+    args
+      .parts()
+      .into_iter()
+      .map(|part| {
+        let source = example_source(solver, part)?;
+        let contents = read_source(&source)?;
+        Ok((part, source, contents))
+      })
+      .collect()
+  } else {
+    let source = input_source(solver, args)?;
+    let contents = read_source(&source)?;
+
+    Ok(
+      args
+        .parts()
+        .into_iter()
+        .map(|part| (part, source.clone(), contents.clone()))
+        .collect(),
+    )
+  }
+}
+
+// Like read_part_inputs, but opens each part's input to be streamed instead. Every part opens its
+// own reader, so stdin can only be streamed once
+fn open_part_inputs(
+  solver: &dyn Solver,
+  args: &SolveArgs,
+) -> Result<Vec<(Part, InputSource, PartInput)>, Error> {
+  let parts = args.parts();
+
+  parts
+    .iter()
+    .map(|part| {
+      let source = if args.input_args.example {
+        example_source(solver, *part)?
+      } else {
+        input_source(solver, args)?
+      };
+
+      if source == InputSource::Stdin && parts.len() > 1 {
+        return Err(Error::new(
+          ErrorKind::InvalidInput,
+          "stdin can only be streamed for a single --part",
+        ));
+      }
+
+      log::info!("streaming contents of {}", source);
+      let reader = source.open()?;
+      Ok((*part, source, PartInput::Stream(reader)))
+    })
+    .collect()
+}
+
+// Whether a file is the crate's own input for the day, however its path was written. An input.txt
+// found through --inputs-dir or $AOC_INPUT_DIR could be anyone's, so it never counts
+fn is_default_input(solver: &dyn Solver, path: &Path) -> bool {
+  let default_path = crate_input_dir()
+    .join(format!("day_{}", solver.day()))
+    .join(INPUT_FILENAME);
+
+  match (path.canonicalize(), default_path.canonicalize()) {
+    (Ok(path), Ok(default_path)) => path == default_path,
+    _ => path == default_path,
+  }
+}
+
+// Embedded examples carry their own answers, and the default input is looked up in the answers
+// file. Any other file or stdin could hold anyone's input, so its answers are never known
+fn expected_answer<'a>(
+  solver: &dyn Solver,
+  part: Part,
+  source: &InputSource,
+  answers: &'a KnownAnswers,
+) -> Option<&'a str> {
+  match source {
+    InputSource::Embedded { .. } => solver
+      .example_for(part)
+      .and_then(|example| example.expected(part)),
+    InputSource::File(path) if is_default_input(solver, path) => {
+      answers.get(solver.day(), INPUT_FILENAME, part)
+    }
+    _ => None,
+  }
+}
+
+// One solved part, in the shape printed by `run --format json`. Unsolved parts have no answer,
+// and parts that failed have no answer but say why
+#[derive(Debug, Serialize)]
+struct RunRecord {
+  day: u8,
+  part: u8,
+  answer: Option<Answer>,
+  elapsed_ms: f64,
+  input_path: String,
+  verified: Option<bool>,
+  error: Option<String>,
+}
+
+// A part with its input already read, ready to be solved on any thread
+enum PartInput {
+  Contents(String),
+  Stream(Box<dyn BufRead + Send>),
+}
+
+struct PartTask {
+  solver: &'static dyn Solver,
+  part: Part,
+  source: InputSource,
+  input: PartInput,
+}
+
+struct SolvedPart {
+  task: PartTask,
+  result: PartResult,
+  elapsed: Duration,
+}
+
+impl PartTask {
+  fn solve(mut self) -> SolvedPart {
+    let (result, elapsed) = time(|| match &mut self.input {
+      PartInput::Contents(contents) => self.solver.solve(self.part, contents),
+      PartInput::Stream(reader) => self.solver.solve_lines(self.part, reader),
+    });
+    SolvedPart {
+      task: self,
+      result,
+      elapsed,
+    }
+  }
+}
+
+// Solves every task on a pool of `jobs` threads, or one per CPU for 0. Results keep the order
+// of the tasks
+fn solve_in_parallel(tasks: Vec<PartTask>, jobs: usize) -> Result<Vec<SolvedPart>, Error> {
+  let pool = ThreadPoolBuilder::new()
+    .num_threads(jobs)
+    .build()
+    .map_err(Error::other)?;
+
+  Ok(pool.install(|| {
+    tasks
+      .into_par_iter()
+      .map(PartTask::solve)
+      .collect()
+  }))
+}
+
+// Returns false if any part couldn't be solved or its answer didn't match the known one
+pub fn run(args: &RunArgs) -> Result<bool, Error> {
+  let solve_args = &args.solve_args;
+  let text = args.format == OutputFormat::Text;
+  let answers = KnownAnswers::load(solve_args.answers.as_deref())?;
+  let mut all_passed = true;
+  let mut records = Vec::new();
+
+  let mut tasks = Vec::new();
+  for solver in select_solvers(solve_args)? {
+    let part_inputs = if args.stream {
+      open_part_inputs(solver, solve_args)?
+    } else {
+      read_part_inputs(solver, solve_args)?
+        .into_iter()
+        .map(|(part, source, contents)| (part, source, PartInput::Contents(contents)))
+        .collect()
+    };
+
+    for (part, source, input) in part_inputs {
+      tasks.push(PartTask {
+        solver,
+        part,
+        source,
+        input,
+      });
+    }
+  }
+
+  // Sequential runs print each part as soon as it's solved, parallel runs once everything is
+  let solved_parts: Box<dyn Iterator<Item = SolvedPart>> = match args.jobs {
+    Some(jobs) => Box::new(solve_in_parallel(tasks, jobs)?.into_iter()),
+    None => Box::new(tasks.into_iter().map(PartTask::solve)),
+  };
+
+  let mut current_day = None;
+  for SolvedPart {
+    task: PartTask {
+      solver,
+      part,
+      source,
+      ..
+    },
+    result,
+    elapsed,
+  } in solved_parts
+  {
+    if text && current_day != Some(solver.day()) {
+      println!("running day {}: {}", solver.day(), solver.title());
+      current_day = Some(solver.day());
+    }
+
+    let (answer, verification, error) = match result {
+      Ok(Some(result)) => {
+        let expected = expected_answer(solver, part, &source, &answers);
+        let verification = Verification::of(expected, &result);
+        if text {
+          println!("{} total {} ({})", part, result, verification);
+        }
+        (Some(result), verification, None)
+      }
+      Ok(None) => {
+        if text {
+          println!("{} not solved yet", part);
+        }
+        (None, Verification::Unknown, None)
+      }
+      Err(error) => {
+        eprintln!("day {} {} {}", solver.day(), part, error);
+        (None, Verification::Unknown, Some(error.to_string()))
+      }
+    };
+
+    all_passed &= error.is_none() && verification.passed() != Some(false);
+    records.push(RunRecord {
+      day: solver.day(),
+      part: part.number(),
+      answer,
+      elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+      input_path: source.path(),
+      verified: verification.passed(),
+      error,
+    });
+  }
+
+  if !text {
+    let json = serde_json::to_string_pretty(&records).map_err(Error::other)?;
+    println!("{}", json);
+  }
+
+  Ok(all_passed)
+}
+
+pub fn list() {
+  for solver in SOLVERS.iter() {
+    println!("day {}: {}", solver.day(), solver.title());
+  }
+}
+
+// Returns false if the part panicked, couldn't parse its input, or didn't produce the expected
+// answer when there is one
+fn check_part(
+  solver: &dyn Solver,
+  part: Part,
+  contents: &str,
+  expected: Option<&str>,
+) -> (bool, String) {
+  let solve = || solver.solve(part, contents);
+
+  match (panic::catch_unwind(AssertUnwindSafe(solve)), expected) {
+    (Err(_), _) => (false, String::from("FAILED (panicked)")),
+    (Ok(Err(error)), _) => (false, format!("FAILED ({})", error)),
+    (Ok(Ok(Some(result))), Some(expected)) if result.to_string() != expected => (
+      false,
+      format!("FAILED (expected {}, got {})", expected, result),
+    ),
+    (Ok(Ok(None)), Some(_)) => (false, String::from("FAILED (not solved yet)")),
+    (Ok(Ok(Some(_))), Some(_)) => (true, String::from("ok")),
+    (Ok(Ok(Some(_))), None) => (true, String::from("ok (answer unknown)")),
+    (Ok(Ok(None)), None) => (true, String::from("not solved yet")),
+  }
+}
+
+// Returns whether every selected day solved without panicking or failing to parse, and matched
+// its known answer when there is one
+fn check_inputs(args: &SolveArgs) -> Result<bool, Error> {
+  let answers = KnownAnswers::load(args.answers.as_deref())?;
+  let mut all_passed = true;
+
+  for solver in select_solvers(args)? {
+    for (part, source, contents) in read_part_inputs(solver, args)? {
+      let expected = expected_answer(solver, part, &source, &answers);
+      let (passed, status) = check_part(solver, part, &contents, expected);
+      println!("day {} {}: {}", solver.day(), part, status);
+      all_passed &= passed;
+    }
+  }
+
+  Ok(all_passed)
+}
+
+// Returns whether every embedded example produced its expected answers
+fn check_examples(args: &SolveArgs) -> Result<bool, Error> {
+  let mut all_passed = true;
+
+  for solver in select_solvers(args)? {
+    for example in solver.examples() {
+      for part in args.parts() {
+        if let Some(expected) = example.expected(part) {
+          let (passed, status) = check_part(solver, part, example.input, Some(expected));
+          println!("day {} {} {}: {}", solver.day(), part, example.name, status);
+          all_passed &= passed;
+        }
+      }
+    }
+  }
+
+  Ok(all_passed)
+}
+
+pub fn check(args: &CheckArgs) -> Result<bool, Error> {
+  if args.examples {
+    check_examples(&args.solve_args)
+  } else {
+    check_inputs(&args.solve_args)
+  }
+}
+
+fn median_of(durations: &[Duration]) -> Duration {
+  Timings::from_durations(durations)
+    .map(|timings| timings.median)
+    .unwrap_or_default()
+}
+
+// Solves the part `runs` times, also timing the day's parser on its own when it has one. None if
+// the part isn't solved yet
+fn bench_part(
+  solver: &dyn Solver,
+  part: Part,
+  contents: &str,
+  runs: u32,
+) -> Result<Option<BenchRecord>, SolveError> {
+  let mut totals = Vec::new();
+  let mut parses = Vec::new();
+
+  for _ in 0..runs {
+    let (result, elapsed) = time(|| solver.solve(part, contents));
+    if result?.is_none() {
+      return Ok(None);
+    }
+    totals.push(elapsed);
+
+    if let (Some(result), elapsed) = time(|| solver.parse(part, contents)) {
+      result?;
+      parses.push(elapsed);
+    }
+  }
+
+  // Solving always includes parsing, so the solve time is whatever parsing doesn't account for
+  let split = if parses.is_empty() {
+    None
+  } else {
+    let parse = median_of(&parses);
+    Some((parse, median_of(&totals).saturating_sub(parse)))
+  };
+
+  Ok(
+    Timings::from_durations(&totals)
+      .map(|total| BenchRecord::new(solver.day(), part, totals.len(), total, split)),
+  )
+}
+
+// Returns false if any part couldn't be solved
+pub fn bench(args: &BenchArgs) -> Result<bool, Error> {
+  let mut all_solved = true;
+  let mut records = Vec::new();
+
+  for solver in select_solvers(&args.solve_args)? {
+    for (part, _, contents) in read_part_inputs(solver, &args.solve_args)? {
+      match bench_part(solver, part, &contents, args.runs) {
+        Ok(Some(record)) => {
+          if !args.json {
+            println!("{}", record);
+          }
+          records.push(record);
+        }
+        Ok(None) => (),
+        Err(error) => {
+          eprintln!("day {} {} {}", solver.day(), part, error);
+          all_solved = false;
+        }
+      }
+    }
+  }
+
+  if args.json {
+    let json = serde_json::to_string_pretty(&records).map_err(Error::other)?;
+    println!("{}", json);
+  }
+
+  Ok(all_solved)
+}
+
+// Returns false if the pipe map couldn't be parsed
+pub fn render(args: &RenderArgs) -> Result<bool, Error> {
+  let solve_args = args.solve_args();
+
+  for solver in select_solvers(&solve_args)? {
+    for (_, source, contents) in read_part_inputs(solver, &solve_args)? {
+      let pipe_map = match PipeMap::from_input(&contents) {
+        Ok(pipe_map) => pipe_map,
+        Err(error) => {
+          eprintln!("{} failed to parse: {}", source, error);
+          return Ok(false);
+        }
+      };
+
+      let options = RenderOptions {
+        charset: args.charset,
+        overlay: args.overlay,
+        color: match args.color {
+          ColorChoice::Auto => std::io::stdout().is_terminal(),
+          ColorChoice::Always => true,
+          ColorChoice::Never => false,
+        },
+      };
+      print!("{}", day_10::render::render(&pipe_map, &options));
+    }
+  }
+
+  Ok(true)
+}
+
+// Returns false if the input couldn't be parsed
+pub fn export(args: &ExportArgs) -> Result<bool, Error> {
+  let format = match args
+    .format
+    .or_else(|| ImageFormat::from_path(&args.output))
+  {
+    Some(format) => format,
+    None => {
+      return Err(Error::new(
+        ErrorKind::InvalidInput,
+        format!(
+          "can't tell the image format of {}, pass --format",
+          args.output.display()
+        ),
+      ))
+    }
+  };
+  let scale = args.scale as usize;
+  let solve_args = args.solve_args();
+
+  for solver in select_solvers(&solve_args)? {
+    for (_, source, contents) in read_part_inputs(solver, &solve_args)? {
+      let image = match solver.day() {
+        3 => Schematic::from_raw_contents(&contents)
+          .map(|schematic| day_3::export::export(&schematic, format, scale)),
+        10 => PipeMap::from_input(&contents)
+          .map(|pipe_map| day_10::export::export(&pipe_map, format, scale)),
+        day => {
+          return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("day {} can't be exported as an image", day),
+          ))
+        }
+      };
+
+      match image {
+        Ok(image) => {
+          std::fs::write(&args.output, image?)?;
+          log::info!("wrote {} to {}", source, args.output.display());
+        }
+        Err(error) => {
+          eprintln!("{} failed to parse: {}", source, error);
+          return Ok(false);
+        }
+      }
+    }
+  }
+
+  Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+  use std::path::PathBuf;
+
+  use super::{expected_answer, solve_in_parallel, PartInput, PartTask};
+  use crate::answers::KnownAnswers;
+  use crate::solver::{find_solver, Part, SOLVERS};
+  use crate::utils::{crate_input_dir, input_search_dirs, InputSource, INPUT_FILENAME};
+
+  #[test]
+  pub fn only_the_default_input_is_checked_against_known_answers() {
+    let solver = find_solver(3).unwrap();
+    let answers = KnownAnswers::load(None).unwrap();
+    let default_path = crate_input_dir()
+      .join("day_3")
+      .join(INPUT_FILENAME);
+    let expected = |source: &InputSource| expected_answer(solver, Part::One, source, &answers);
+
+    assert_eq!(
+      expected(&InputSource::File(default_path.clone())),
+      Some("560670")
+    );
+
+    // However the path to the default input is written, it's still checked
+    let roundabout_path = crate_input_dir()
+      .join("day_4")
+      .join("..")
+      .join("day_3")
+      .join(INPUT_FILENAME);
+    assert_eq!(
+      expected(&InputSource::File(roundabout_path)),
+      Some("560670")
+    );
+
+    // Somebody else's input with the same file name
+    let other_path = PathBuf::from("other/day_3/input.txt");
+    assert_eq!(expected(&InputSource::File(other_path)), None);
+    assert_eq!(expected(&InputSource::Stdin), None);
+  }
+
+  // An input.txt under --inputs-dir is found the same way as the crate's own, but could belong to
+  // anyone, even when it holds the very same puzzle
+  #[test]
+  pub fn inputs_dir_files_are_not_checked_against_known_answers() {
+    let solver = find_solver(3).unwrap();
+    let answers = KnownAnswers::load(None).unwrap();
+
+    let inputs_dir = std::env::temp_dir().join(format!("aoc_2023_inputs_{}", std::process::id()));
+    fs::create_dir_all(inputs_dir.join("day_3")).unwrap();
+    fs::copy(
+      crate_input_dir()
+        .join("day_3")
+        .join(INPUT_FILENAME),
+      inputs_dir.join("day_3").join(INPUT_FILENAME),
+    )
+    .unwrap();
+
+    let source =
+      InputSource::from_search_dirs(&input_search_dirs(Some(&inputs_dir)), 3, INPUT_FILENAME);
+    let expected = source
+      .as_ref()
+      .ok()
+      .and_then(|source| expected_answer(solver, Part::One, source, &answers));
+    fs::remove_dir_all(&inputs_dir).unwrap();
+
+    assert_eq!(
+      source.unwrap(),
+      InputSource::File(inputs_dir.join("day_3").join(INPUT_FILENAME))
+    );
+    assert_eq!(expected, None);
+  }
+
+  #[test]
+  pub fn parallel_results_keep_the_order_of_the_tasks() {
+    let mut tasks = Vec::new();
+    for solver in SOLVERS.iter() {
+      for part in Part::ALL {
+        if let Some(example) = solver.example_for(part) {
+          tasks.push(PartTask {
+            solver: *solver,
+            part,
+            source: InputSource::Embedded {
+              name: example.name.to_string(),
+              contents: example.input,
+            },
+            input: PartInput::Contents(example.input.to_string()),
+          });
+        }
+      }
+    }
+    let order = tasks
+      .iter()
+      .map(|task| (task.solver.day(), task.part))
+      .collect::<Vec<_>>();
+    assert!(order.len() > 4);
+
+    let solved_parts = solve_in_parallel(tasks, 4).unwrap();
+
+    assert_eq!(
+      solved_parts
+        .iter()
+        .map(|solved| (solved.task.solver.day(), solved.task.part))
+        .collect::<Vec<_>>(),
+      order
+    );
+    assert!(solved_parts
+      .iter()
+      .all(|solved| solved.result.is_ok()));
+  }
+}
@@ -0,0 +1,366 @@
+use std::fmt;
+use std::io::BufRead;
+
+use serde::{Serialize, Serializer};
+
+use crate::parse::{read_error, ParseError};
+use crate::{day_1, day_10, day_2, day_3, day_4, day_5, day_6, day_7, day_8, day_9};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+  One,
+  Two,
+}
+
+impl Part {
+  pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+  pub fn from_number(number: u8) -> Option<Part> {
+    match number {
+      1 => Some(Part::One),
+      2 => Some(Part::Two),
+      _ => None,
+    }
+  }
+
+  pub fn number(&self) -> u8 {
+    match self {
+      Part::One => 1,
+      Part::Two => 2,
+    }
+  }
+}
+
+impl fmt::Display for Part {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "part_{}", self.number())
+  }
+}
+
+// An example input from the puzzle description, embedded into the binary at compile time along
+// with the answers the puzzle gives for it
+#[derive(Debug)]
+pub struct Example {
+  pub name: &'static str,
+  pub input: &'static str,
+  pub part_1: Option<&'static str>,
+  pub part_2: Option<&'static str>,
+}
+
+impl Example {
+  pub fn expected(&self, part: Part) -> Option<&'static str> {
+    match part {
+      Part::One => self.part_1,
+      Part::Two => self.part_2,
+    }
+  }
+}
+
+// Any day's answer. Integers are widened so that every day's native type fits without wrapping,
+// and signed and unsigned answers with the same value compare equal
+#[derive(Debug, Clone, Eq)]
+pub enum Answer {
+  Unsigned(u128),
+  Signed(i128),
+  Text(String),
+}
+
+impl Answer {
+  // Numeric answers as a signed value, when they fit
+  fn as_signed(&self) -> Option<i128> {
+    match self {
+      Answer::Unsigned(value) => i128::try_from(*value).ok(),
+      Answer::Signed(value) => Some(*value),
+      Answer::Text(_) => None,
+    }
+  }
+}
+
+impl PartialEq for Answer {
+  fn eq(&self, other: &Answer) -> bool {
+    match (self, other) {
+      (Answer::Text(text), Answer::Text(other_text)) => text == other_text,
+      (Answer::Unsigned(value), Answer::Unsigned(other_value)) => value == other_value,
+      _ => match (self.as_signed(), other.as_signed()) {
+        (Some(value), Some(other_value)) => value == other_value,
+        _ => false,
+      },
+    }
+  }
+}
+
+impl Serialize for Answer {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    match self {
+      Answer::Unsigned(value) => serializer.serialize_u128(*value),
+      Answer::Signed(value) => serializer.serialize_i128(*value),
+      Answer::Text(text) => serializer.serialize_str(text),
+    }
+  }
+}
+
+impl fmt::Display for Answer {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Answer::Unsigned(value) => write!(f, "{}", value),
+      Answer::Signed(value) => write!(f, "{}", value),
+      Answer::Text(text) => write!(f, "{}", text),
+    }
+  }
+}
+
+macro_rules! answer_from {
+  ($variant:ident, $wide:ty, $($narrow:ty),*) => {
+    $(
+      impl From<$narrow> for Answer {
+        fn from(value: $narrow) -> Answer {
+          Answer::$variant(value as $wide)
+        }
+      }
+    )*
+  };
+}
+
+answer_from!(Unsigned, u128, u8, u16, u32, u64, u128, usize);
+answer_from!(Signed, i128, i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+  fn from(text: String) -> Answer {
+    Answer::Text(text)
+  }
+}
+
+impl From<&str> for Answer {
+  fn from(text: &str) -> Answer {
+    Answer::Text(text.to_string())
+  }
+}
+
+// Why a part has no answer. Input that parsed can still be impossible to solve, like a total
+// that doesn't fit in the type the day works it out in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+  Parse(ParseError),
+  Overflow {
+    day: u8,
+    line: usize,
+    message: String,
+  },
+}
+
+impl SolveError {
+  // `line` is the 1-based input line that pushed the answer out of range
+  pub fn overflow(day: u8, line: usize, message: impl Into<String>) -> SolveError {
+    SolveError::Overflow {
+      day,
+      line,
+      message: message.into(),
+    }
+  }
+}
+
+impl From<ParseError> for SolveError {
+  fn from(error: ParseError) -> SolveError {
+    SolveError::Parse(error)
+  }
+}
+
+impl fmt::Display for SolveError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      SolveError::Parse(error) => write!(f, "failed to parse input: {}", error),
+      SolveError::Overflow { day, line, message } => {
+        write!(f, "failed to solve: day {} line {}: {}", day, line, message)
+      }
+    }
+  }
+}
+
+impl std::error::Error for SolveError {}
+
+// Ok(None) means the part hasn't been solved yet
+pub type PartResult = Result<Option<Answer>, SolveError>;
+
+// A single day's puzzle. Each day_N module exposes one of these and registers it in SOLVERS
+pub trait Solver: Sync {
+  fn day(&self) -> u8;
+
+  fn title(&self) -> &'static str;
+
+  fn examples(&self) -> &'static [Example];
+
+  fn part_1(&self, input: &str) -> PartResult;
+
+  fn part_2(&self, input: &str) -> PartResult;
+
+  // Solves a part from input that is still being read. Days that never need all of their input at
+  // once override this to work a line at a time, the rest read everything first
+  fn solve_lines(&self, part: Part, reader: &mut dyn BufRead) -> PartResult {
+    let mut input = String::new();
+    reader
+      .read_to_string(&mut input)
+      .map_err(|error| read_error(self.day(), 1, error))?;
+    self.solve(part, &input)
+  }
+
+  // Only parses the input for a part, so that benchmarks can split parsing from solving. Days
+  // that parse and solve in a single pass return None
+  fn parse(&self, _part: Part, _input: &str) -> Option<Result<(), ParseError>> {
+    None
+  }
+
+  // Only runs the requested part, so a slow or unsolved part never holds up the other one
+  fn solve(&self, part: Part, input: &str) -> PartResult {
+    match part {
+      Part::One => self.part_1(input),
+      Part::Two => self.part_2(input),
+    }
+  }
+
+  // The first example with a known answer for the part, falling back to the first example
+  fn example_for(&self, part: Part) -> Option<&'static Example> {
+    self
+      .examples()
+      .iter()
+      .find(|example| example.expected(part).is_some())
+      .or_else(|| self.examples().first())
+  }
+}
+
+// Every available day, in order. Adding a new day only requires adding it here
+pub static SOLVERS: &[&dyn Solver] = &[
+  &day_1::Day1,
+  &day_2::Day2,
+  &day_3::Day3,
+  &day_4::Day4,
+  &day_5::Day5,
+  &day_6::Day6,
+  &day_7::Day7,
+  &day_8::Day8,
+  &day_9::Day9,
+  &day_10::Day10,
+];
+
+pub fn find_solver(day: u8) -> Option<&'static dyn Solver> {
+  SOLVERS
+    .iter()
+    .find(|solver| solver.day() == day)
+    .copied()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{find_solver, Answer, Part, SOLVERS};
+
+  #[test]
+  pub fn solvers_are_registered_in_day_order() {
+    let days = SOLVERS
+      .iter()
+      .map(|solver| solver.day())
+      .collect::<Vec<_>>();
+    assert_eq!(days, (1..=10).collect::<Vec<_>>());
+  }
+
+  #[test]
+  pub fn find_solver_works() {
+    assert_eq!(find_solver(3).unwrap().day(), 3);
+    assert!(find_solver(25).is_none());
+  }
+
+  #[test]
+  pub fn examples_match_expected_answers() {
+    for solver in SOLVERS.iter() {
+      assert!(
+        !solver.examples().is_empty(),
+        "day {} has no examples",
+        solver.day()
+      );
+
+      for example in solver.examples() {
+        for part in Part::ALL {
+          if let Some(expected) = example.expected(part) {
+            let result = solver.solve(part, example.input).unwrap();
+            assert_eq!(
+              result.map(|answer| answer.to_string()).as_deref(),
+              Some(expected),
+              "day {} {} {}",
+              solver.day(),
+              part,
+              example.name
+            );
+          }
+        }
+      }
+    }
+  }
+
+  // Inputs saved on Windows, or with extra blank lines at the end, have to give the same answers
+  #[test]
+  pub fn examples_ignore_line_endings_and_trailing_newlines() {
+    for solver in SOLVERS.iter() {
+      for example in solver.examples() {
+        let windows_input = example.input.trim_end().replace('\n', "\r\n") + "\r\n\r\n";
+
+        for part in Part::ALL {
+          if let Some(expected) = example.expected(part) {
+            let result = solver.solve(part, &windows_input).unwrap();
+            assert_eq!(
+              result.map(|answer| answer.to_string()).as_deref(),
+              Some(expected),
+              "day {} {} {}",
+              solver.day(),
+              part,
+              example.name
+            );
+          }
+        }
+      }
+    }
+  }
+
+  #[test]
+  pub fn example_for_prefers_examples_with_answers() {
+    let day_1 = find_solver(1).unwrap();
+    assert_eq!(
+      day_1.example_for(Part::Two).unwrap().name,
+      "part_2_example_input.txt"
+    );
+    let day_10 = find_solver(10).unwrap();
+    assert_eq!(
+      day_10.example_for(Part::Two).unwrap().name,
+      "example_input_3.txt"
+    );
+  }
+
+  #[test]
+  pub fn solve_only_runs_the_requested_part() {
+    let day_10 = find_solver(10).unwrap();
+    let contents = "..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...";
+    assert_eq!(
+      day_10.solve(Part::One, contents),
+      Ok(Some(Answer::Unsigned(8)))
+    );
+    assert_eq!(
+      day_10.solve(Part::Two, contents),
+      Ok(Some(Answer::Unsigned(1)))
+    );
+  }
+
+  #[test]
+  pub fn answers_compare_by_value() {
+    assert_eq!(Answer::from(114u32), Answer::from(114i64));
+    assert_ne!(Answer::from(-2i32), Answer::from(2u64));
+    assert_ne!(Answer::from(8u32), Answer::from("8"));
+    assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+  }
+
+  #[test]
+  pub fn answers_serialize_as_json_numbers_or_strings() {
+    let answers = [
+      Answer::from(12u32),
+      Answer::from(-3i64),
+      Answer::from("LRL"),
+    ];
+    assert_eq!(serde_json::to_string(&answers).unwrap(), "[12,-3,\"LRL\"]");
+  }
+}
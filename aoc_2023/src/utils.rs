@@ -1,14 +1,18 @@
+use std::fmt;
 use std::fs::File;
-use std::io::{prelude::*, Error};
-use std::path::Path;
+use std::io::{prelude::*, BufReader, Error, ErrorKind};
+use std::path::{Path, PathBuf};
 
-pub fn read_input(filename: &str) -> Result<String, Error> {
+pub const INPUT_DIR_ENV_VAR: &str = "AOC_INPUT_DIR";
+pub const INPUT_FILENAME: &str = "input.txt";
+
+pub fn read_input(filename: impl AsRef<Path>) -> Result<String, Error> {
   // Create a path to the desired file
-  let path = Path::new(filename);
+  let path = filename.as_ref();
   // let display = path.display();
 
   // Open the path in read-only mode, returns `io::Result<File>`
-  let mut file = match File::open(&path) {
+  let mut file = match File::open(path) {
     Err(why) => {
       return Err(why);
     }
@@ -22,3 +26,177 @@ pub fn read_input(filename: &str) -> Result<String, Error> {
     Ok(_) => Ok(contents),
   }
 }
+
+// The crate's own src directory, home of the inputs that answers.toml was written for
+pub fn crate_input_dir() -> PathBuf {
+  Path::new(env!("CARGO_MANIFEST_DIR")).join("src")
+}
+
+// Directories searched for day_N input directories, in order. An explicit directory is the only
+// one searched, otherwise $AOC_INPUT_DIR (if set) is tried before the crate's own src directory.
+// The crate directory is baked in at compile time so the working directory never matters
+pub fn input_search_dirs(explicit_dir: Option<&Path>) -> Vec<PathBuf> {
+  if let Some(dir) = explicit_dir {
+    return vec![dir.to_path_buf()];
+  }
+
+  let mut dirs = Vec::new();
+  if let Some(dir) = std::env::var_os(INPUT_DIR_ENV_VAR) {
+    dirs.push(PathBuf::from(dir));
+  }
+  dirs.push(crate_input_dir());
+
+  dirs
+}
+
+// Input files are laid out as <search_dir>/day_<N>/<filename>
+pub fn find_day_input(search_dirs: &[PathBuf], day: u8, filename: &str) -> Result<PathBuf, Error> {
+  let relative_path = Path::new(&format!("day_{}", day)).join(filename);
+
+  let candidates = search_dirs
+    .iter()
+    .map(|dir| dir.join(&relative_path))
+    .collect::<Vec<_>>();
+
+  match candidates.iter().find(|path| path.is_file()) {
+    Some(path) => Ok(path.clone()),
+    None => {
+      let searched = candidates
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+      Err(Error::new(
+        ErrorKind::NotFound,
+        format!(
+          "could not find {} (searched: {})",
+          relative_path.display(),
+          searched
+        ),
+      ))
+    }
+  }
+}
+
+// Reads one of a day's input files from the default search locations
+pub fn read_day_input(day: u8, filename: &str) -> Result<String, Error> {
+  read_input(find_day_input(&input_search_dirs(None), day, filename)?)
+}
+
+// Where a day's puzzle input comes from
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+  File(PathBuf),
+  Stdin,
+  // Compiled into the binary, see solver::Example
+  Embedded {
+    name: String,
+    contents: &'static str,
+  },
+}
+
+impl InputSource {
+  // "-" means stdin, anything else is treated as a file path
+  pub fn from_arg(arg: &Path) -> InputSource {
+    if arg == Path::new("-") {
+      InputSource::Stdin
+    } else {
+      InputSource::File(arg.to_path_buf())
+    }
+  }
+
+  pub fn from_search_dirs(
+    search_dirs: &[PathBuf],
+    day: u8,
+    filename: &str,
+  ) -> Result<InputSource, Error> {
+    Ok(InputSource::File(find_day_input(
+      search_dirs,
+      day,
+      filename,
+    )?))
+  }
+
+  // Where the input came from, for reports. Stdin is "-" like on the command line
+  pub fn path(&self) -> String {
+    match self {
+      InputSource::File(path) => path.display().to_string(),
+      InputSource::Stdin => String::from("-"),
+      InputSource::Embedded { name, .. } => name.clone(),
+    }
+  }
+
+  // Opens the input to be read a piece at a time instead of all at once
+  pub fn open(&self) -> Result<Box<dyn BufRead + Send>, Error> {
+    match self {
+      InputSource::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+      InputSource::Stdin => Ok(Box::new(BufReader::new(std::io::stdin()))),
+      InputSource::Embedded { contents, .. } => Ok(Box::new(contents.as_bytes())),
+    }
+  }
+
+  pub fn read(&self) -> Result<String, Error> {
+    match self {
+      InputSource::File(path) => read_input(path),
+      InputSource::Stdin => {
+        let mut contents = String::new();
+        std::io::stdin().read_to_string(&mut contents)?;
+        Ok(contents)
+      }
+      InputSource::Embedded { contents, .. } => Ok(contents.to_string()),
+    }
+  }
+}
+
+impl fmt::Display for InputSource {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      InputSource::File(path) => write!(f, "{}", path.display()),
+      InputSource::Stdin => write!(f, "stdin"),
+      InputSource::Embedded { name, .. } => write!(f, "{} (embedded)", name),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::ErrorKind;
+  use std::path::{Path, PathBuf};
+
+  use super::{find_day_input, input_search_dirs, read_day_input, InputSource, INPUT_FILENAME};
+
+  #[test]
+  pub fn dash_means_stdin() {
+    assert_eq!(InputSource::from_arg(Path::new("-")), InputSource::Stdin);
+    assert_eq!(
+      InputSource::from_arg(Path::new("my_input.txt")),
+      InputSource::File(PathBuf::from("my_input.txt"))
+    );
+  }
+
+  #[test]
+  pub fn explicit_dir_is_the_only_search_dir() {
+    let dirs = input_search_dirs(Some(Path::new("inputs")));
+    assert_eq!(dirs, vec![PathBuf::from("inputs")]);
+  }
+
+  #[test]
+  pub fn default_search_dirs_are_anchored_at_the_crate() {
+    let dirs = input_search_dirs(None);
+    let crate_src_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    assert_eq!(dirs.last(), Some(&crate_src_dir));
+    assert!(read_day_input(1, INPUT_FILENAME).is_ok());
+  }
+
+  #[test]
+  pub fn missing_input_names_searched_locations() {
+    let dirs = vec![PathBuf::from("first"), PathBuf::from("second")];
+    let error = find_day_input(&dirs, 7, INPUT_FILENAME).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::NotFound);
+    assert_eq!(
+      error.to_string(),
+      "could not find day_7/input.txt (searched: first/day_7/input.txt, second/day_7/input.txt)"
+    );
+  }
+}
@@ -0,0 +1,31 @@
+use aoc_2023::day_10::PipeMap;
+use aoc_2023::day_3::Schematic;
+use aoc_2023::solver::{find_solver, Answer, Part, SOLVERS};
+
+#[test]
+pub fn every_day_is_reachable_through_the_solver_registry() {
+  for solver in SOLVERS.iter() {
+    let example = solver.example_for(Part::One).unwrap();
+    assert!(solver.solve(Part::One, example.input).is_ok());
+  }
+
+  let day_6 = find_solver(6).unwrap();
+  assert_eq!(
+    day_6.solve(Part::Two, "Time: 7 15 30\nDistance: 9 40 200"),
+    Ok(Some(Answer::from(71503u64)))
+  );
+}
+
+#[test]
+pub fn day_types_can_be_used_directly() {
+  let schematic = Schematic::from_raw_contents("467..114..\n...*......\n..35..633.").unwrap();
+  assert_eq!(schematic.get_valid_grid_number_sum(), 467 + 35);
+
+  let pipe_map = PipeMap::from_input(".....\n.S-7.\n.|.|.\n.L-J.\n.....").unwrap();
+  let max_distance = pipe_map
+    .build_pipe_loop_with_distances()
+    .into_iter()
+    .map(|(distance, _)| distance)
+    .max();
+  assert_eq!(max_distance, Some(4));
+}